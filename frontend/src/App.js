@@ -21,7 +21,7 @@ function isValidIndex(index, list) {
 }
 export default function App() {
     const { config, loading, error, updateConfig } = useConfig();
-    const { status, isBuilding, start, startAndRun, cancel, clearLogs, clearToken } = useBuild();
+    const { status, isBuilding, start, startAndRun, cancel, clearLogs, clearToken, notice } = useBuild();
     const [selectedIndex, setSelectedIndex] = useState(null);
     const [uiMessage, setUiMessage] = useState(null);
     const [engineCandidates, setEngineCandidates] = useState([]);
@@ -64,10 +64,8 @@ export default function App() {
         runDetection();
     }, [config, hasCheckedEngines, loading, updateConfig]);
     const canBuild = useMemo(() => {
-        return isValidIndex(selectedIndex, config.projects)
-            && Boolean(config.unrealEnginePath)
-            && !isBuilding;
-    }, [selectedIndex, config.projects, config.unrealEnginePath, isBuilding]);
+        return isValidIndex(selectedIndex, config.projects) && !isBuilding;
+    }, [selectedIndex, config.projects, isBuilding]);
     const handleAddProject = async () => {
         setUiMessage(null);
         const projectPath = await selectProjectPath();
@@ -128,7 +126,7 @@ export default function App() {
         setEngineDetectError(null);
         setEngineCandidates([]);
         try {
-            const result = await detectEngines();
+            const result = await detectEngines(true);
             if (result.installs.length === 1) {
                 const install = result.installs[0];
                 await updateConfig({
@@ -154,13 +152,14 @@ export default function App() {
         if (isBuilding) {
             return;
         }
-        if (!isValidIndex(selectedIndex, config.projects) || !config.unrealEnginePath) {
-            setUiMessage('Select a project and engine path first.');
+        if (!isValidIndex(selectedIndex, config.projects)) {
+            setUiMessage('Select a project first.');
             return;
         }
+        // The backend picks the engine from the project's EngineAssociation and
+        // falls back to the configured engine path.
         await start({
-            projectPath: config.projects[selectedIndex].path,
-            unrealEnginePath: config.unrealEnginePath
+            projectPath: config.projects[selectedIndex].path
         });
     };
     const handleBuildAndRun = async () => {
@@ -168,14 +167,13 @@ export default function App() {
         if (isBuilding) {
             return;
         }
-        if (!isValidIndex(selectedIndex, config.projects) || !config.unrealEnginePath) {
-            setUiMessage('Select a project and engine path first.');
+        if (!isValidIndex(selectedIndex, config.projects)) {
+            setUiMessage('Select a project first.');
             return;
         }
         await startAndRun({
-            projectPath: config.projects[selectedIndex].path,
-            unrealEnginePath: config.unrealEnginePath
+            projectPath: config.projects[selectedIndex].path
         });
     };
-    return (_jsxs(_Fragment, { children: [_jsx(Starfield, {}), _jsxs("div", { className: "app", children: [_jsx(Header, {}), _jsxs("div", { className: "grid", children: [_jsxs("section", { className: "panel", children: [_jsxs("div", { className: "panel-header", children: [_jsxs("div", { children: [_jsx("p", { className: "section-label", children: "Projects" }), _jsx("p", { className: "muted", children: "Your Unreal project list" })] }), _jsx("button", { className: "primary-button", type: "button", onClick: handleAddProject, children: "Add Project" })] }), loading ? (_jsx("div", { className: "panel-message", children: "Loading configuration..." })) : (_jsx(ProjectList, { projects: config.projects, selectedIndex: selectedIndex, onSelect: setSelectedIndex, onRemove: handleRemoveProject }))] }), _jsxs("section", { className: "panel", children: [_jsx(EnginePathCard, { enginePath: config.unrealEnginePath, onSelect: handleSelectEnginePath, detectedEngines: engineCandidates, onPickDetected: handlePickDetected, detectError: engineDetectError, onRetryDetect: handleRetryDetect }), _jsx(BuildControls, { canBuild: canBuild, isBuilding: isBuilding, status: status, onBuild: handleBuild, onBuildAndRun: handleBuildAndRun, onCancel: cancel, onClearLogs: clearLogs }), _jsx(TerminalPanel, { clearToken: clearToken })] })] }), (uiMessage || error) && (_jsx("div", { className: "alert", children: uiMessage ?? error }))] })] }));
+    return (_jsxs(_Fragment, { children: [_jsx(Starfield, {}), _jsxs("div", { className: "app", children: [_jsx(Header, {}), _jsxs("div", { className: "grid", children: [_jsxs("section", { className: "panel", children: [_jsxs("div", { className: "panel-header", children: [_jsxs("div", { children: [_jsx("p", { className: "section-label", children: "Projects" }), _jsx("p", { className: "muted", children: "Your Unreal project list" })] }), _jsx("button", { className: "primary-button", type: "button", onClick: handleAddProject, children: "Add Project" })] }), loading ? (_jsx("div", { className: "panel-message", children: "Loading configuration..." })) : (_jsx(ProjectList, { projects: config.projects, selectedIndex: selectedIndex, onSelect: setSelectedIndex, onRemove: handleRemoveProject }))] }), _jsxs("section", { className: "panel", children: [_jsx(EnginePathCard, { enginePath: config.unrealEnginePath, onSelect: handleSelectEnginePath, detectedEngines: engineCandidates, onPickDetected: handlePickDetected, detectError: engineDetectError, onRetryDetect: handleRetryDetect }), _jsx(BuildControls, { canBuild: canBuild, isBuilding: isBuilding, status: status, onBuild: handleBuild, onBuildAndRun: handleBuildAndRun, onCancel: cancel, onClearLogs: clearLogs }), _jsx(TerminalPanel, { clearToken: clearToken, notice: notice })] })] }), (uiMessage || error) && (_jsx("div", { className: "alert", children: uiMessage ?? error }))] })] }));
 }
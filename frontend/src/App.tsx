@@ -24,7 +24,8 @@ function isValidIndex(index: number | null, list: ProjectConfig[]): index is num
 
 export default function App() {
   const { config, loading, error, updateConfig } = useConfig();
-  const { status, isBuilding, start, startAndRun, cancel, clearLogs, clearToken } = useBuild();
+  const { status, isBuilding, start, startAndRun, cancel, clearLogs, clearToken, notice } =
+    useBuild();
   const [selectedIndex, setSelectedIndex] = useState<number | null>(null);
   const [uiMessage, setUiMessage] = useState<string | null>(null);
   const [engineCandidates, setEngineCandidates] = useState<EngineInstall[]>([]);
@@ -71,10 +72,8 @@ export default function App() {
   }, [config, hasCheckedEngines, loading, updateConfig]);
 
   const canBuild = useMemo(() => {
-    return isValidIndex(selectedIndex, config.projects)
-      && Boolean(config.unrealEnginePath)
-      && !isBuilding;
-  }, [selectedIndex, config.projects, config.unrealEnginePath, isBuilding]);
+    return isValidIndex(selectedIndex, config.projects) && !isBuilding;
+  }, [selectedIndex, config.projects, isBuilding]);
 
   const handleAddProject = async () => {
     setUiMessage(null);
@@ -145,7 +144,7 @@ export default function App() {
     setEngineCandidates([]);
 
     try {
-      const result = await detectEngines();
+      const result = await detectEngines(true);
       if (result.installs.length === 1) {
         const install = result.installs[0];
         await updateConfig({
@@ -170,14 +169,15 @@ export default function App() {
     if (isBuilding) {
       return;
     }
-    if (!isValidIndex(selectedIndex, config.projects) || !config.unrealEnginePath) {
-      setUiMessage('Select a project and engine path first.');
+    if (!isValidIndex(selectedIndex, config.projects)) {
+      setUiMessage('Select a project first.');
       return;
     }
 
+    // The backend picks the engine from the project's EngineAssociation and
+    // falls back to the configured engine path.
     await start({
-      projectPath: config.projects[selectedIndex].path,
-      unrealEnginePath: config.unrealEnginePath
+      projectPath: config.projects[selectedIndex].path
     });
   };
 
@@ -186,14 +186,13 @@ export default function App() {
     if (isBuilding) {
       return;
     }
-    if (!isValidIndex(selectedIndex, config.projects) || !config.unrealEnginePath) {
-      setUiMessage('Select a project and engine path first.');
+    if (!isValidIndex(selectedIndex, config.projects)) {
+      setUiMessage('Select a project first.');
       return;
     }
 
     await startAndRun({
-      projectPath: config.projects[selectedIndex].path,
-      unrealEnginePath: config.unrealEnginePath
+      projectPath: config.projects[selectedIndex].path
     });
   };
 
@@ -243,7 +242,7 @@ export default function App() {
               onCancel={cancel}
               onClearLogs={clearLogs}
             />
-            <TerminalPanel clearToken={clearToken} />
+            <TerminalPanel clearToken={clearToken} notice={notice} />
           </section>
         </div>
 
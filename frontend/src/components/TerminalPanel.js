@@ -4,7 +4,8 @@ import { useEffect, useRef } from 'react';
 import { listen } from '@tauri-apps/api/event';
 import { Terminal } from 'xterm';
 import { FitAddon } from 'xterm-addon-fit';
-export default function TerminalPanel({ clearToken }) {
+import { ackBuildLog } from '../services/backend';
+export default function TerminalPanel({ clearToken, notice }) {
     const containerRef = useRef(null);
     const terminalRef = useRef(null);
     const fitAddonRef = useRef(null);
@@ -46,12 +47,35 @@ export default function TerminalPanel({ clearToken }) {
             terminalRef.current.clear();
         }
     }, [clearToken]);
+    useEffect(() => {
+        const terminal = terminalRef.current;
+        if (!terminal || !notice) {
+            return;
+        }
+        for (const line of notice.lines) {
+            terminal.writeln(line);
+        }
+    }, [notice]);
     useEffect(() => {
         let cancelled = false;
         let unlisten = null;
         const setup = async () => {
-            const fn = await listen('build-log', (event) => {
-                terminalRef.current?.writeln(event.payload.line);
+            const fn = await listen('build-log-chunk', (event) => {
+                const { buildId, lines, skipped } = event.payload;
+                const terminal = terminalRef.current;
+                if (!terminal) {
+                    return;
+                }
+                if (skipped > 0) {
+                    terminal.writeln(`... ${skipped} lines skipped to keep up with the build ...`);
+                }
+                const text = lines.map((entry) => entry.line).join('\r\n');
+                const lastSeq = lines.length > 0 ? lines[lines.length - 1].seq : null;
+                terminal.write(lines.length > 0 ? `${text}\r\n` : '', () => {
+                    if (lastSeq !== null) {
+                        void ackBuildLog(buildId, lastSeq).catch(() => undefined);
+                    }
+                });
             });
             if (cancelled) {
                 fn();
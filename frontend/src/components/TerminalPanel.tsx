@@ -3,17 +3,22 @@ import { useEffect, useRef } from 'react';
 import { listen, type UnlistenFn } from '@tauri-apps/api/event';
 import { Terminal } from 'xterm';
 import { FitAddon } from 'xterm-addon-fit';
-
-type BuildLogPayload = {
-  buildId: string;
-  line: string;
+import type { BuildLogChunk } from '@shared/types';
+import { ackBuildLog } from '../services/backend';
+
+// Lines from outside the build log, such as editor launch warnings. A new
+// token writes them once.
+export type TerminalNotice = {
+  token: number;
+  lines: string[];
 };
 
 type TerminalPanelProps = {
   clearToken: number;
+  notice?: TerminalNotice | null;
 };
 
-export default function TerminalPanel({ clearToken }: TerminalPanelProps) {
+export default function TerminalPanel({ clearToken, notice }: TerminalPanelProps) {
   const containerRef = useRef<HTMLDivElement | null>(null);
   const terminalRef = useRef<Terminal | null>(null);
   const fitAddonRef = useRef<FitAddon | null>(null);
@@ -64,13 +69,39 @@ export default function TerminalPanel({ clearToken }: TerminalPanelProps) {
     }
   }, [clearToken]);
 
+  useEffect(() => {
+    const terminal = terminalRef.current;
+    if (!terminal || !notice) {
+      return;
+    }
+    for (const line of notice.lines) {
+      terminal.writeln(line);
+    }
+  }, [notice]);
+
   useEffect(() => {
     let cancelled = false;
     let unlisten: UnlistenFn | null = null;
 
     const setup = async () => {
-      const fn = await listen<BuildLogPayload>('build-log', (event) => {
-        terminalRef.current?.writeln(event.payload.line);
+      const fn = await listen<BuildLogChunk>('build-log-chunk', (event) => {
+        const { buildId, lines, skipped } = event.payload;
+        const terminal = terminalRef.current;
+        if (!terminal) {
+          return;
+        }
+
+        if (skipped > 0) {
+          terminal.writeln(`... ${skipped} lines skipped to keep up with the build ...`);
+        }
+
+        const text = lines.map((entry) => entry.line).join('\r\n');
+        const lastSeq = lines.length > 0 ? lines[lines.length - 1].seq : null;
+        terminal.write(lines.length > 0 ? `${text}\r\n` : '', () => {
+          if (lastSeq !== null) {
+            void ackBuildLog(buildId, lastSeq).catch(() => undefined);
+          }
+        });
       });
 
       if (cancelled) {
@@ -13,6 +13,7 @@ export function useBuild() {
     const [status, setStatus] = useState(INITIAL_STATUS);
     const [buildId, setBuildId] = useState(null);
     const [clearToken, setClearToken] = useState(0);
+    const [notice, setNotice] = useState(null);
     const pollTimerRef = useRef(null);
     const runAfterBuildRef = useRef(null);
     const clearLogs = useCallback(() => {
@@ -108,10 +109,18 @@ export function useBuild() {
                     const payload = runAfterBuildRef.current;
                     runAfterBuildRef.current = null;
                     try {
-                        await launchEditor(payload);
+                        const warnings = await launchEditor(payload);
+                        if (warnings.length > 0) {
+                            setNotice((prev) => ({ token: (prev?.token ?? 0) + 1, lines: warnings }));
+                        }
                     }
                     catch (err) {
                         console.error('Failed to launch editor after build:', err);
+                        const message = err instanceof Error ? err.message : String(err);
+                        setNotice((prev) => ({
+                            token: (prev?.token ?? 0) + 1,
+                            lines: [`Failed to launch editor: ${message}`]
+                        }));
                     }
                 }
                 else {
@@ -145,6 +154,7 @@ export function useBuild() {
         buildId,
         status,
         clearToken,
+        notice,
         isBuilding: status.status === 'running',
         start,
         startAndRun,
@@ -1,6 +1,7 @@
 // React hook for build lifecycle and status polling.
 import { useCallback, useEffect, useRef, useState } from 'react';
 import type { BuildStartRequest, BuildStatus } from '@shared/types';
+import type { TerminalNotice } from '../components/TerminalPanel';
 import { cancelBuild, getBuildStatus, launchEditor, startBuild } from '../services/backend';
 
 const INITIAL_STATUS: BuildStatus = {
@@ -17,6 +18,7 @@ export function useBuild() {
   const [status, setStatus] = useState<BuildStatus>(INITIAL_STATUS);
   const [buildId, setBuildId] = useState<string | null>(null);
   const [clearToken, setClearToken] = useState(0);
+  const [notice, setNotice] = useState<TerminalNotice | null>(null);
   const pollTimerRef = useRef<number | null>(null);
   const runAfterBuildRef = useRef<BuildStartRequest | null>(null);
 
@@ -122,9 +124,17 @@ export function useBuild() {
           const payload = runAfterBuildRef.current;
           runAfterBuildRef.current = null;
           try {
-            await launchEditor(payload);
+            const warnings = await launchEditor(payload);
+            if (warnings.length > 0) {
+              setNotice((prev) => ({ token: (prev?.token ?? 0) + 1, lines: warnings }));
+            }
           } catch (err) {
             console.error('Failed to launch editor after build:', err);
+            const message = err instanceof Error ? err.message : String(err);
+            setNotice((prev) => ({
+              token: (prev?.token ?? 0) + 1,
+              lines: [`Failed to launch editor: ${message}`]
+            }));
           }
         } else {
           // Build finished with non-success status, clear the run-after flag
@@ -160,6 +170,7 @@ export function useBuild() {
     buildId,
     status,
     clearToken,
+    notice,
     isBuilding: status.status === 'running',
     start,
     startAndRun,
@@ -9,27 +9,38 @@ export async function saveConfig(config) {
 export async function startBuild(payload) {
     const buildId = await invoke('start_build', {
         projectPath: payload.projectPath,
-        unrealEnginePath: payload.unrealEnginePath
+        unrealEnginePath: payload.unrealEnginePath,
+        timing: payload.timing
     });
     return { buildId };
 }
 export async function getBuildStatus(buildId) {
     return invoke('get_build_status', { buildId });
 }
-export async function getBuildLogs(buildId, from) {
-    return invoke('get_build_logs', { buildId, from });
+export async function getBuildLogs(buildId, from, page) {
+    return invoke('get_build_logs', {
+        buildId,
+        from,
+        pageSize: page?.pageSize,
+        maxBytes: page?.maxBytes
+    });
+}
+export async function ackBuildLog(buildId, seq) {
+    await invoke('ack_build_log', { buildId, seq });
 }
 export async function cancelBuild(buildId) {
     await invoke('cancel_build', { buildId });
 }
+// Resolves to warnings about the chosen engine, e.g. an EngineAssociation mismatch.
 export async function launchEditor(payload) {
-    await invoke('launch_editor', {
+    return invoke('launch_editor', {
         projectPath: payload.projectPath,
         unrealEnginePath: payload.unrealEnginePath
     });
 }
-export async function detectEngines() {
-    const installs = await invoke('detect_engines');
+// Served from the backend's cache; pass refresh to rescan the disk.
+export async function detectEngines(refresh) {
+    const installs = await invoke('detect_engines', { refresh });
     return { installs };
 }
 // No longer needed - Tauri commands are always available
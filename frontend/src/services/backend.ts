@@ -17,11 +17,14 @@ export async function saveConfig(config: Config): Promise<void> {
 
 export async function startBuild(payload: {
   projectPath: string;
-  unrealEnginePath: string;
+  // Omit to use the engine from the project's EngineAssociation.
+  unrealEnginePath?: string | null;
+  timing?: boolean;
 }): Promise<{ buildId: string }> {
   const buildId = await invoke<string>('start_build', {
     projectPath: payload.projectPath,
-    unrealEnginePath: payload.unrealEnginePath
+    unrealEnginePath: payload.unrealEnginePath,
+    timing: payload.timing
   });
   return { buildId };
 }
@@ -30,26 +33,41 @@ export async function getBuildStatus(buildId: string): Promise<BuildStatus> {
   return invoke<BuildStatus>('get_build_status', { buildId });
 }
 
-export async function getBuildLogs(buildId: string, from: number): Promise<BuildLogsResponse> {
-  return invoke<BuildLogsResponse>('get_build_logs', { buildId, from });
+export async function getBuildLogs(
+  buildId: string,
+  from: number,
+  page?: { pageSize?: number; maxBytes?: number }
+): Promise<BuildLogsResponse> {
+  return invoke<BuildLogsResponse>('get_build_logs', {
+    buildId,
+    from,
+    pageSize: page?.pageSize,
+    maxBytes: page?.maxBytes
+  });
+}
+
+export async function ackBuildLog(buildId: string, seq: number): Promise<void> {
+  await invoke('ack_build_log', { buildId, seq });
 }
 
 export async function cancelBuild(buildId: string): Promise<void> {
   await invoke('cancel_build', { buildId });
 }
 
+// Resolves to warnings about the chosen engine, e.g. an EngineAssociation mismatch.
 export async function launchEditor(payload: {
   projectPath: string;
-  unrealEnginePath: string;
-}): Promise<void> {
-  await invoke('launch_editor', {
+  unrealEnginePath?: string | null;
+}): Promise<string[]> {
+  return invoke<string[]>('launch_editor', {
     projectPath: payload.projectPath,
     unrealEnginePath: payload.unrealEnginePath
   });
 }
 
-export async function detectEngines(): Promise<{ installs: EngineInstall[] }> {
-  const installs = await invoke<EngineInstall[]>('detect_engines');
+// Served from the backend's cache; pass refresh to rescan the disk.
+export async function detectEngines(refresh?: boolean): Promise<{ installs: EngineInstall[] }> {
+  const installs = await invoke<EngineInstall[]>('detect_engines', { refresh });
   return { installs };
 }
 
@@ -1,24 +1,86 @@
 // Shared domain types used across UI and backend.
+export type RetryPolicy = {
+  enabled: boolean;
+  maxAttempts?: number;
+  backoffSeconds?: number;
+  backoffMultiplier?: number;
+  maxBackoffSeconds?: number;
+  patterns?: string[];
+};
+
 export type ProjectConfig = {
   name: string;
   path: string;
+  retry?: RetryPolicy;
+};
+
+// Per-engine settings, keyed by EngineInstall.id in Config.engineProfiles.
+export type EngineProfile = {
+  label?: string;
+  defaultPlatform?: string;
+  extraUbtArgs?: string[];
+  dotnetPath?: string;
+  env?: Record<string, string>;
 };
 
 export type Config = {
   projects: ProjectConfig[];
   unrealEnginePath: string | null;
+  logRetentionDays?: number;
+  logRetentionMaxMb?: number;
+  keepFinishedBuilds?: number;
+  keepFinishedBuildsMinutes?: number;
+  regressionThresholdPercent?: number;
+  regressionBaselineBuilds?: number;
+  knownFailureRuleFiles?: string[];
+  buildOutputEncoding?: string | null;
+  engineScanRoots?: string[];
+  manualEngines?: string[];
+  engineProfiles?: Record<string, EngineProfile>;
 };
 
+export type BuildVersion = {
+  majorVersion: number;
+  minorVersion: number;
+  patchVersion: number;
+  changelist: number;
+  compatibleChangelist: number;
+  branchName: string;
+  isLicenseeVersion: boolean;
+};
+
+export type EngineKind = 'launcher' | 'installedBuild' | 'source';
+
+export type EngineSource =
+  | 'knownLocation'
+  | 'launcherManifest'
+  | 'registry'
+  | 'scanRoot'
+  | 'manual'
+  | 'association';
+
 export type EngineInstall = {
   id: string;
   name: string;
   path: string;
   version: string | null;
+  buildVersion?: BuildVersion | null;
+  kind: EngineKind;
+  source: EngineSource;
+  guid?: string | null;
+};
+
+// Payload of the `engines-changed` event.
+export type EnginesChanged = {
+  added: EngineInstall[];
+  removed: EngineInstall[];
+  updated: EngineInstall[];
 };
 
 export type BuildStartRequest = {
   projectPath: string;
-  unrealEnginePath: string;
+  // Explicit engine override; omit to use the project's EngineAssociation.
+  unrealEnginePath?: string | null;
 };
 
 export type BuildStartResponse = {
@@ -33,9 +95,25 @@ export type BuildStatus = {
   finishedAt: string | null;
 };
 
+export type LogStream = 'stdout' | 'stderr' | 'system';
+
+export type LogLine = {
+  seq: number;
+  timestamp: string;
+  stream: LogStream;
+  line: string;
+};
+
+export type BuildLogChunk = {
+  buildId: string;
+  lines: LogLine[];
+  skipped: number;
+};
+
 export type BuildLogsResponse = {
-  lines: string[];
+  lines: LogLine[];
   nextIndex: number;
+  totalLines: number;
   finished: boolean;
 };
 
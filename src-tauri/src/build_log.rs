@@ -0,0 +1,527 @@
+// Build log storage: in-memory lines, an on-disk copy and a running summary.
+use crate::diagnostics::{parse_action_progress, parse_diagnostic, Diagnostic, Severity};
+use crate::known_failures::{FailureHint, KnownFailureRules};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashSet, VecDeque};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, LineWriter, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BuildSummary {
+    pub warnings: usize,
+    pub errors: usize,
+    #[serde(rename = "lineCount")]
+    pub line_count: usize,
+    #[serde(rename = "actionsCompleted")]
+    pub actions_completed: usize,
+    #[serde(rename = "actionsTotal")]
+    pub actions_total: usize,
+    /// Distinct diagnostics, in the order they first appeared.
+    #[serde(default)]
+    pub diagnostics: Vec<Diagnostic>,
+    #[serde(default, rename = "diagnosticsTruncated")]
+    pub diagnostics_truncated: bool,
+    /// Known failures recognised in the output, with suggested fixes.
+    #[serde(default)]
+    pub hints: Vec<FailureHint>,
+    #[serde(skip)]
+    seen_diagnostics: HashSet<Diagnostic>,
+}
+
+/// Distinct diagnostics kept per build; counts keep going past this.
+const MAX_STORED_DIAGNOSTICS: usize = 1_000;
+
+impl BuildSummary {
+    fn observe_hints(&mut self, seq: usize, line: &str, rules: &KnownFailureRules) {
+        for rule in rules.matches(line) {
+            match self.hints.iter_mut().find(|hint| hint.rule_id == rule.id) {
+                Some(hint) => hint.occurrences += 1,
+                None => self.hints.push(FailureHint {
+                    rule_id: rule.id.clone(),
+                    title: rule.title.clone(),
+                    explanation: rule.explanation.clone(),
+                    fix: rule.fix.clone(),
+                    first_line: line.to_string(),
+                    first_seq: seq,
+                    occurrences: 1,
+                }),
+            }
+        }
+    }
+
+    fn observe(&mut self, line: &str) {
+        self.line_count += 1;
+
+        if let Some((completed, total)) = parse_action_progress(line) {
+            self.actions_completed = self.actions_completed.max(completed);
+            self.actions_total = self.actions_total.max(total);
+            return;
+        }
+
+        let Some(diagnostic) = parse_diagnostic(line) else {
+            return;
+        };
+        match diagnostic.severity {
+            Severity::Error => self.errors += 1,
+            Severity::Warning => self.warnings += 1,
+        }
+
+        if self.seen_diagnostics.contains(&diagnostic) {
+            return;
+        }
+        if self.diagnostics.len() >= MAX_STORED_DIAGNOSTICS {
+            self.diagnostics_truncated = true;
+            return;
+        }
+        self.seen_diagnostics.insert(diagnostic.clone());
+        self.diagnostics.push(diagnostic);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogStream {
+    Stdout,
+    Stderr,
+    /// Messages generated by Stellar itself rather than the build process.
+    System,
+}
+
+impl LogStream {
+    fn as_str(self) -> &'static str {
+        match self {
+            LogStream::Stdout => "stdout",
+            LogStream::Stderr => "stderr",
+            LogStream::System => "system",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "stdout" => Some(LogStream::Stdout),
+            "stderr" => Some(LogStream::Stderr),
+            "system" => Some(LogStream::System),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogLine {
+    /// Position in the build log, assigned in arrival order across all streams.
+    pub seq: usize,
+    pub timestamp: String,
+    pub stream: LogStream,
+    #[serde(rename = "line")]
+    pub text: String,
+}
+
+impl LogLine {
+    /// On-disk form: `<timestamp> [<stream>] <text>`.
+    fn to_disk(&self) -> String {
+        format!(
+            "{} [{}] {}",
+            self.timestamp,
+            self.stream.as_str(),
+            self.text
+        )
+    }
+
+    fn from_disk(seq: usize, raw: &str) -> Self {
+        let parsed = raw.split_once(' ').and_then(|(timestamp, rest)| {
+            let (stream, text) = rest.strip_prefix('[')?.split_once("] ")?;
+            Some((timestamp, LogStream::parse(stream)?, text))
+        });
+
+        match parsed {
+            Some((timestamp, stream, text)) => LogLine {
+                seq,
+                timestamp: timestamp.to_string(),
+                stream,
+                text: text.to_string(),
+            },
+            None => LogLine {
+                seq,
+                timestamp: String::new(),
+                stream: LogStream::Stdout,
+                text: raw.to_string(),
+            },
+        }
+    }
+}
+
+/// Number of most recent lines kept in memory; older lines are served from disk.
+const MEMORY_LINE_LIMIT: usize = 20_000;
+
+/// A byte offset into the log file is remembered every `INDEX_STRIDE` lines so
+/// pages of evicted lines can be read without scanning the whole file.
+const INDEX_STRIDE: usize = 1024;
+
+#[derive(Debug, Clone, Copy)]
+pub struct PageLimits {
+    pub max_lines: usize,
+    pub max_bytes: usize,
+}
+
+pub struct LogPage {
+    pub lines: Vec<LogLine>,
+    pub next_index: usize,
+}
+
+/// Where to read a page from once the log lock has been released.
+pub enum LogSource {
+    Memory(LogPage),
+    Disk(DiskRange),
+}
+
+/// Every line of a build log, for consumers that need to scan the whole thing.
+pub enum FullLog {
+    Memory(Vec<LogLine>),
+    Disk(PathBuf),
+}
+
+pub struct DiskRange {
+    path: PathBuf,
+    offset: u64,
+    offset_index: usize,
+    end_index: usize,
+}
+
+pub struct BuildLog {
+    lines: VecDeque<LogLine>,
+    first_index: usize,
+    /// Covers the whole build, across retries.
+    summary: BuildSummary,
+    /// Covers only the current attempt of a retried build.
+    attempt_summary: BuildSummary,
+    path: PathBuf,
+    file: Option<LineWriter<File>>,
+    bytes_written: u64,
+    disk_lines: usize,
+    offsets: Vec<u64>,
+    rules: Arc<KnownFailureRules>,
+}
+
+impl BuildLog {
+    /// Creates a log that mirrors every line to `path` as it arrives.
+    /// If the file cannot be created the log stays memory-only.
+    pub fn new(path: &Path, rules: Arc<KnownFailureRules>) -> Self {
+        let file = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| {
+                OpenOptions::new()
+                    .create(true)
+                    .write(true)
+                    .truncate(true)
+                    .open(path)
+            })
+            .map_err(|e| eprintln!("Failed to open build log {:?}: {}", path, e))
+            .ok()
+            .map(LineWriter::new);
+
+        BuildLog {
+            lines: VecDeque::new(),
+            first_index: 0,
+            summary: BuildSummary::default(),
+            attempt_summary: BuildSummary::default(),
+            path: path.to_path_buf(),
+            file,
+            bytes_written: 0,
+            disk_lines: 0,
+            offsets: Vec::new(),
+            rules,
+        }
+    }
+
+    /// Appends text, stamping each line with the next sequence number and the
+    /// current time. Multi-line text, such as a regex error in a system message,
+    /// becomes one line per row so sequence numbers match the on-disk lines.
+    pub fn push(&mut self, stream: LogStream, text: String) -> Vec<LogLine> {
+        if !text.contains('\n') {
+            return vec![self.push_line(stream, text)];
+        }
+        text.split('\n')
+            .map(|row| self.push_line(stream, row.trim_end_matches('\r').to_string()))
+            .collect()
+    }
+
+    fn push_line(&mut self, stream: LogStream, text: String) -> LogLine {
+        if stream != LogStream::System {
+            self.summary.observe_hints(self.len(), &text, &self.rules);
+            self.attempt_summary
+                .observe_hints(self.len(), &text, &self.rules);
+        }
+        self.summary.observe(&text);
+        self.attempt_summary.observe(&text);
+
+        let line = LogLine {
+            seq: self.len(),
+            timestamp: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            stream,
+            text,
+        };
+
+        if let Some(file) = self.file.as_mut() {
+            if line.seq.is_multiple_of(INDEX_STRIDE) {
+                self.offsets.push(self.bytes_written);
+            }
+            let disk_line = line.to_disk();
+            if writeln!(file, "{}", disk_line).is_ok() {
+                self.bytes_written += disk_line.len() as u64 + 1;
+                self.disk_lines += 1;
+            } else {
+                // Stop writing after the first failure (e.g. disk full) rather than
+                // retrying on every line.
+                self.file = None;
+            }
+        }
+
+        self.lines.push_back(line.clone());
+        if self.lines.len() > MEMORY_LINE_LIMIT {
+            self.lines.pop_front();
+            self.first_index += 1;
+        }
+        line
+    }
+
+    /// Flushes and closes the on-disk copy once the build has finished.
+    pub fn close(&mut self) {
+        if let Some(mut file) = self.file.take() {
+            let _ = file.flush();
+        }
+    }
+
+    /// Total number of lines received, including those no longer held in memory.
+    pub fn len(&self) -> usize {
+        self.first_index + self.lines.len()
+    }
+
+    /// Plans a page starting at `from`. Lines still in memory are copied right
+    /// away; older lines must be read from disk with [`read_disk_page`].
+    pub fn page(&self, from: usize, limits: PageLimits) -> LogSource {
+        // Lines that fell out of memory are only recoverable if the disk copy
+        // was written up to that point; otherwise skip ahead to what is left.
+        if from < self.first_index && self.disk_lines >= self.first_index {
+            let stride = (from / INDEX_STRIDE).min(self.offsets.len().saturating_sub(1));
+            return LogSource::Disk(DiskRange {
+                path: self.path.clone(),
+                offset: self.offsets.get(stride).copied().unwrap_or(0),
+                offset_index: stride * INDEX_STRIDE,
+                end_index: self.first_index,
+            });
+        }
+
+        let start = from.max(self.first_index);
+        let mut page = LogPage {
+            lines: Vec::new(),
+            next_index: start,
+        };
+        let mut bytes = 0;
+        for line in self.lines.iter().skip(start - self.first_index) {
+            if !page.lines.is_empty()
+                && (page.lines.len() >= limits.max_lines
+                    || bytes + line.text.len() > limits.max_bytes)
+            {
+                break;
+            }
+            bytes += line.text.len();
+            page.lines.push(line.clone());
+        }
+        page.next_index = start + page.lines.len();
+        LogSource::Memory(page)
+    }
+
+    pub fn summary(&self) -> &BuildSummary {
+        &self.summary
+    }
+
+    /// Ends the current attempt and returns its summary. The build-wide summary
+    /// keeps accumulating.
+    pub fn begin_attempt(&mut self) -> BuildSummary {
+        std::mem::take(&mut self.attempt_summary)
+    }
+
+    /// Prefers the disk copy; memory is only used when lines never made it to disk,
+    /// in which case lines evicted from memory are unavailable.
+    pub fn full_log(&self) -> FullLog {
+        if self.disk_lines == self.len() && self.path.exists() {
+            FullLog::Disk(self.path.clone())
+        } else {
+            FullLog::Memory(self.lines.iter().cloned().collect())
+        }
+    }
+}
+
+impl FullLog {
+    /// Visits every line in sequence order.
+    pub fn for_each_line(self, mut visit: impl FnMut(LogLine)) -> Result<(), String> {
+        match self {
+            FullLog::Memory(lines) => lines.into_iter().for_each(visit),
+            FullLog::Disk(path) => {
+                let file =
+                    File::open(&path).map_err(|e| format!("Failed to open build log: {}", e))?;
+                for (index, raw) in BufReader::new(file).split(b'\n').enumerate() {
+                    let raw = raw.map_err(|e| format!("Failed to read build log: {}", e))?;
+                    let raw = String::from_utf8_lossy(&raw);
+                    visit(LogLine::from_disk(index, raw.trim_end_matches('\r')));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl DiskRange {
+    /// A range covering a whole log file, for builds no longer held in memory.
+    pub fn whole_file(path: PathBuf) -> Self {
+        DiskRange {
+            path,
+            offset: 0,
+            offset_index: 0,
+            end_index: usize::MAX,
+        }
+    }
+}
+
+/// Reads a page of lines starting at `from` from an on-disk build log.
+pub fn read_disk_page(
+    range: &DiskRange,
+    from: usize,
+    limits: PageLimits,
+) -> Result<LogPage, String> {
+    let file = File::open(&range.path).map_err(|e| format!("Failed to open build log: {}", e))?;
+    let mut reader = BufReader::new(file);
+    reader
+        .seek(SeekFrom::Start(range.offset))
+        .map_err(|e| format!("Failed to read build log: {}", e))?;
+
+    let mut page = LogPage {
+        lines: Vec::new(),
+        next_index: from,
+    };
+    let mut bytes = 0;
+    let mut index = range.offset_index;
+    let mut buf = Vec::new();
+    while index < range.end_index {
+        buf.clear();
+        let read = reader
+            .read_until(b'\n', &mut buf)
+            .map_err(|e| format!("Failed to read build log: {}", e))?;
+        if read == 0 {
+            break;
+        }
+        if index >= from {
+            let raw = String::from_utf8_lossy(&buf);
+            let line = LogLine::from_disk(index, raw.trim_end_matches(['\n', '\r']));
+            if !page.lines.is_empty()
+                && (page.lines.len() >= limits.max_lines
+                    || bytes + line.text.len() > limits.max_bytes)
+            {
+                break;
+            }
+            bytes += line.text.len();
+            page.lines.push(line);
+        }
+        index += 1;
+    }
+    page.next_index = from.max(range.offset_index) + page.lines.len();
+    Ok(page)
+}
+
+pub fn log_file_path(log_dir: &Path, build_id: &str) -> PathBuf {
+    log_dir.join(format!("{}.log", build_id))
+}
+
+/// Deletes build logs older than `max_age`, then the oldest remaining logs until
+/// the directory fits in `max_bytes`. Logs listed in `active` are never touched.
+pub fn prune_logs(log_dir: &Path, max_age: Duration, max_bytes: u64, active: &HashSet<PathBuf>) {
+    let entries = match fs::read_dir(log_dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+
+    let now = SystemTime::now();
+    let mut logs = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("log") || active.contains(&path) {
+            continue;
+        }
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        let modified = metadata.modified().unwrap_or(now);
+        let age = now.duration_since(modified).unwrap_or_default();
+        if age > max_age {
+            let _ = fs::remove_file(&path);
+            continue;
+        }
+        logs.push((modified, metadata.len(), path));
+    }
+
+    // Newest first, so the oldest logs are the ones dropped once over budget.
+    logs.sort_by_key(|(modified, _, _)| std::cmp::Reverse(*modified));
+    let mut total = 0u64;
+    for (_, size, path) in logs {
+        total += size;
+        if total > max_bytes {
+            let _ = fs::remove_file(&path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multi_line_messages_keep_disk_sequence_numbers() {
+        let path =
+            std::env::temp_dir().join(format!("stellar-build-log-{}.log", uuid::Uuid::new_v4()));
+        let (rules, _) = KnownFailureRules::load(&[]);
+        let mut log = BuildLog::new(&path, Arc::new(rules));
+        log.push(LogStream::Stdout, "Building MyGame...".to_string());
+        let pushed = log.push(
+            LogStream::System,
+            "regex parse error:\n    (abc\n    ^\nerror: unclosed group".to_string(),
+        );
+        log.push(LogStream::Stdout, "Total time 1.2s".to_string());
+        log.close();
+
+        assert_eq!(pushed.len(), 4);
+        assert_eq!(log.len(), 6);
+
+        let limits = PageLimits {
+            max_lines: 100,
+            max_bytes: usize::MAX,
+        };
+        let page = read_disk_page(&DiskRange::whole_file(path.clone()), 0, limits).unwrap();
+        let lines: Vec<(usize, LogStream, &str)> = page
+            .lines
+            .iter()
+            .map(|line| (line.seq, line.stream, line.text.as_str()))
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                (0, LogStream::Stdout, "Building MyGame..."),
+                (1, LogStream::System, "regex parse error:"),
+                (2, LogStream::System, "    (abc"),
+                (3, LogStream::System, "    ^"),
+                (4, LogStream::System, "error: unclosed group"),
+                (5, LogStream::Stdout, "Total time 1.2s"),
+            ]
+        );
+
+        let tail = read_disk_page(&DiskRange::whole_file(path.clone()), 5, limits).unwrap();
+        assert_eq!(tail.lines.len(), 1);
+        assert_eq!(tail.lines[0].text, "Total time 1.2s");
+        assert_eq!(tail.next_index, 6);
+
+        let _ = fs::remove_file(&path);
+    }
+}
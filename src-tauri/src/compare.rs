@@ -0,0 +1,215 @@
+// Structured comparison of two build records.
+use crate::diagnostics::Diagnostic;
+use crate::history::BuildRecord;
+use serde::Serialize;
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub base: Option<String>,
+    pub head: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NumberDelta {
+    pub base: Option<i64>,
+    pub head: Option<i64>,
+    pub delta: Option<i64>,
+    /// Change relative to the base value, e.g. `25.0` for 25% slower.
+    #[serde(rename = "deltaPercent")]
+    pub delta_percent: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiagnosticDiff {
+    /// Present in the head build but not in the base build.
+    pub new: Vec<Diagnostic>,
+    /// Present in the base build but gone from the head build.
+    pub fixed: Vec<Diagnostic>,
+    pub unchanged: Vec<Diagnostic>,
+    /// Set when either build hit the stored-diagnostics cap, so the diff may be incomplete.
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BuildComparison {
+    #[serde(rename = "baseId")]
+    pub base_id: String,
+    #[serde(rename = "headId")]
+    pub head_id: String,
+    /// Status of each build, e.g. `success` -> `error`.
+    pub status: FieldChange,
+    #[serde(rename = "parameterChanges")]
+    pub parameter_changes: Vec<FieldChange>,
+    #[serde(rename = "addedArguments")]
+    pub added_arguments: Vec<String>,
+    #[serde(rename = "removedArguments")]
+    pub removed_arguments: Vec<String>,
+    #[serde(rename = "engineChanges")]
+    pub engine_changes: Vec<FieldChange>,
+    pub diagnostics: DiagnosticDiff,
+    #[serde(rename = "durationMs")]
+    pub duration_ms: NumberDelta,
+    pub actions: NumberDelta,
+    pub warnings: NumberDelta,
+    pub errors: NumberDelta,
+}
+
+fn field_change(field: &str, base: Option<&str>, head: Option<&str>) -> FieldChange {
+    FieldChange {
+        field: field.to_string(),
+        base: base.map(str::to_string),
+        head: head.map(str::to_string),
+    }
+}
+
+fn collect_changes(fields: &[(&str, Option<&str>, Option<&str>)]) -> Vec<FieldChange> {
+    fields
+        .iter()
+        .filter(|(_, base, head)| base != head)
+        .map(|(field, base, head)| field_change(field, *base, *head))
+        .collect()
+}
+
+fn number_delta(base: Option<i64>, head: Option<i64>) -> NumberDelta {
+    let delta = base.zip(head).map(|(base, head)| head - base);
+    let delta_percent = base
+        .zip(delta)
+        .filter(|(base, _)| *base != 0)
+        .map(|(base, delta)| delta as f64 * 100.0 / base as f64);
+    NumberDelta {
+        base,
+        head,
+        delta,
+        delta_percent,
+    }
+}
+
+fn diff_diagnostics(
+    base: &[Diagnostic],
+    head: &[Diagnostic],
+) -> (Vec<Diagnostic>, Vec<Diagnostic>, Vec<Diagnostic>) {
+    let base_keys: HashSet<_> = base.iter().map(Diagnostic::key).collect();
+    let head_keys: HashSet<_> = head.iter().map(Diagnostic::key).collect();
+
+    // The same diagnostic can be stored more than once with different line numbers;
+    // report each key once.
+    let mut reported = HashSet::new();
+    let mut new = Vec::new();
+    let mut unchanged = Vec::new();
+    for diagnostic in head {
+        let key = diagnostic.key();
+        if !reported.insert(key.clone()) {
+            continue;
+        }
+        if base_keys.contains(&key) {
+            unchanged.push(diagnostic.clone());
+        } else {
+            new.push(diagnostic.clone());
+        }
+    }
+
+    let fixed = base
+        .iter()
+        .filter(|diagnostic| {
+            let key = diagnostic.key();
+            !head_keys.contains(&key) && reported.insert(key)
+        })
+        .cloned()
+        .collect();
+
+    (new, fixed, unchanged)
+}
+
+pub fn compare_builds(base: &BuildRecord, head: &BuildRecord) -> BuildComparison {
+    let parameter_changes = collect_changes(&[
+        (
+            "projectPath",
+            Some(&base.project_path),
+            Some(&head.project_path),
+        ),
+        ("target", Some(&base.target), Some(&head.target)),
+        ("platform", Some(&base.platform), Some(&head.platform)),
+        (
+            "configuration",
+            Some(&base.configuration),
+            Some(&head.configuration),
+        ),
+    ]);
+
+    let engine_changes = collect_changes(&[
+        (
+            "enginePath",
+            Some(&base.engine_path),
+            Some(&head.engine_path),
+        ),
+        (
+            "engineVersion",
+            base.engine_version.as_deref(),
+            head.engine_version.as_deref(),
+        ),
+    ]);
+
+    let added_arguments = head
+        .arguments
+        .iter()
+        .filter(|arg| !base.arguments.contains(arg))
+        .cloned()
+        .collect();
+    let removed_arguments = base
+        .arguments
+        .iter()
+        .filter(|arg| !head.arguments.contains(arg))
+        .cloned()
+        .collect();
+
+    let empty = Vec::new();
+    let base_diagnostics = base.summary.as_ref().map_or(&empty, |s| &s.diagnostics);
+    let head_diagnostics = head.summary.as_ref().map_or(&empty, |s| &s.diagnostics);
+    let (new, fixed, unchanged) = diff_diagnostics(base_diagnostics, head_diagnostics);
+    let truncated = [base, head].iter().any(|record| {
+        record
+            .summary
+            .as_ref()
+            .is_some_and(|summary| summary.diagnostics_truncated)
+    });
+
+    let summary_value =
+        |record: &BuildRecord, pick: fn(&crate::build_log::BuildSummary) -> usize| {
+            record.summary.as_ref().map(|summary| pick(summary) as i64)
+        };
+
+    BuildComparison {
+        base_id: base.id.clone(),
+        head_id: head.id.clone(),
+        status: field_change(
+            "status",
+            Some(&base.status.status),
+            Some(&head.status.status),
+        ),
+        parameter_changes,
+        added_arguments,
+        removed_arguments,
+        engine_changes,
+        diagnostics: DiagnosticDiff {
+            new,
+            fixed,
+            unchanged,
+            truncated,
+        },
+        duration_ms: number_delta(base.duration_ms, head.duration_ms),
+        actions: number_delta(
+            summary_value(base, |s| s.actions_total),
+            summary_value(head, |s| s.actions_total),
+        ),
+        warnings: number_delta(
+            summary_value(base, |s| s.warnings),
+            summary_value(head, |s| s.warnings),
+        ),
+        errors: number_delta(
+            summary_value(base, |s| s.errors),
+            summary_value(head, |s| s.errors),
+        ),
+    }
+}
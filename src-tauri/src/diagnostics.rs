@@ -0,0 +1,131 @@
+// Parsing of compiler and UnrealBuildTool diagnostics from build output.
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub file: Option<String>,
+    pub line: Option<u32>,
+    pub column: Option<u32>,
+    pub code: Option<String>,
+    pub message: String,
+}
+
+/// Identity used when comparing diagnostics across builds. Line and column are
+/// left out because unrelated edits shift them between builds.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DiagnosticKey {
+    severity: Severity,
+    file: Option<String>,
+    code: Option<String>,
+    message: String,
+}
+
+impl Diagnostic {
+    pub fn key(&self) -> DiagnosticKey {
+        DiagnosticKey {
+            severity: self.severity,
+            file: self
+                .file
+                .as_ref()
+                .map(|file| file.replace('\\', "/").to_lowercase()),
+            code: self.code.clone(),
+            message: self.message.clone(),
+        }
+    }
+}
+
+fn msvc_regex() -> &'static Regex {
+    // C:\Path\File.cpp(12): error C2065: 'Foo': undeclared identifier
+    // C:\Path\File.cpp(12,5): warning C4996: ...
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(
+            r"^\s*(?P<file>[^\s(][^(]*?)\((?P<line>\d+)(?:,(?P<col>\d+))?\)\s*:\s*(?:fatal\s+)?(?P<sev>error|warning)\s*(?P<code>[A-Z]+\d+)?\s*:\s*(?P<msg>.*)$",
+        )
+        .expect("valid msvc diagnostic regex")
+    })
+}
+
+fn clang_regex() -> &'static Regex {
+    // /path/File.cpp:12:5: error: use of undeclared identifier 'Foo'
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(
+            r"^\s*(?P<file>(?:[A-Za-z]:)?[^:]+):(?P<line>\d+):(?:(?P<col>\d+):)?\s*(?:fatal\s+)?(?P<sev>error|warning):\s*(?P<msg>.*?)(?:\s*\[(?P<code>-W[^\]]+)\])?$",
+        )
+        .expect("valid clang diagnostic regex")
+    })
+}
+
+fn generic_regex() -> &'static Regex {
+    // LogCompile: Error: ..., ERROR: ..., UnrealBuildTool : error : ...
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"(?i)^\s*(?:[A-Za-z][\w ]*?\s*:\s*)?(?P<sev>error|warning)\s*(?P<code>[A-Z]+\d+)?\s*:\s*(?P<msg>.+)$")
+            .expect("valid generic diagnostic regex")
+    })
+}
+
+fn action_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"^\s*\[(\d+)/(\d+)\]").expect("valid action regex"))
+}
+
+fn parse_severity(value: &str) -> Severity {
+    if value.eq_ignore_ascii_case("error") {
+        Severity::Error
+    } else {
+        Severity::Warning
+    }
+}
+
+/// Extracts a compiler or UBT diagnostic from a single line of build output.
+pub fn parse_diagnostic(line: &str) -> Option<Diagnostic> {
+    let number = |caps: &regex::Captures, name: &str| {
+        caps.name(name).and_then(|m| m.as_str().parse::<u32>().ok())
+    };
+    let text =
+        |caps: &regex::Captures, name: &str| caps.name(name).map(|m| m.as_str().trim().to_string());
+
+    if let Some(caps) = msvc_regex()
+        .captures(line)
+        .or_else(|| clang_regex().captures(line))
+    {
+        return Some(Diagnostic {
+            severity: parse_severity(&caps["sev"]),
+            file: text(&caps, "file"),
+            line: number(&caps, "line"),
+            column: number(&caps, "col"),
+            code: text(&caps, "code"),
+            message: caps["msg"].trim().to_string(),
+        });
+    }
+
+    let caps = generic_regex().captures(line)?;
+    Some(Diagnostic {
+        severity: parse_severity(&caps["sev"]),
+        file: None,
+        line: None,
+        column: None,
+        code: text(&caps, "code"),
+        message: caps["msg"].trim().to_string(),
+    })
+}
+
+/// Returns `(completed, total)` for UBT action progress lines such as `[12/345] Compile Foo.cpp`.
+pub fn parse_action_progress(line: &str) -> Option<(usize, usize)> {
+    let caps = action_regex().captures(line)?;
+    let completed = caps.get(1)?.as_str().parse().ok()?;
+    let total = caps.get(2)?.as_str().parse().ok()?;
+    Some((completed, total))
+}
@@ -0,0 +1,288 @@
+// The `EngineAssociation` field of a `.uproject`, which names the engine a
+// project was made with.
+use crate::engine_registry::path_key;
+use crate::{EngineInstall, EngineKind};
+use std::fs;
+use std::ops::Range;
+use std::path::{Component, Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EngineAssociation {
+    /// No association: the project lives inside an engine source tree.
+    Native,
+    /// A launcher version such as `5.3`.
+    Version(String),
+    /// The GUID of a registered source engine.
+    Guid(String),
+    /// A path to the engine root, relative to the project directory or absolute.
+    Path(PathBuf),
+}
+
+/// Reads the raw `EngineAssociation` value; `None` when the field is absent.
+pub fn read_association(project_path: &Path) -> Result<Option<String>, String> {
+    let contents = fs::read_to_string(project_path)
+        .map_err(|e| format!("Failed to read project file: {}", e))?;
+    let project: serde_json::Value = serde_json::from_str(&contents)
+        .map_err(|e| format!("Failed to parse project file: {}", e))?;
+    Ok(project
+        .get("EngineAssociation")
+        .and_then(|value| value.as_str())
+        .map(str::to_string))
+}
+
+fn is_guid(value: &str) -> bool {
+    let hex = value.trim_start_matches('{').trim_end_matches('}');
+    let dashed = hex.len() == 36
+        && hex.chars().enumerate().all(|(index, c)| match index {
+            8 | 13 | 18 | 23 => c == '-',
+            _ => c.is_ascii_hexdigit(),
+        });
+    dashed || (hex.len() == 32 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+fn is_version(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .split('.')
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+}
+
+pub fn parse_association(value: &str, project_dir: &Path) -> EngineAssociation {
+    let value = value.trim();
+    if value.is_empty() {
+        EngineAssociation::Native
+    } else if is_guid(value) {
+        EngineAssociation::Guid(value.to_string())
+    } else if is_version(value) {
+        EngineAssociation::Version(value.to_string())
+    } else {
+        EngineAssociation::Path(project_dir.join(value))
+    }
+}
+
+fn normalize_guid(guid: &str) -> String {
+    guid.trim_start_matches('{')
+        .trim_end_matches('}')
+        .replace('-', "")
+        .to_lowercase()
+}
+
+/// Finds the detected engine an association refers to. Path and native
+/// associations may point at engines that were not detected; callers can fall
+/// back to [`association_root`] for those.
+pub fn find_install<'a>(
+    association: &EngineAssociation,
+    installs: &'a [EngineInstall],
+) -> Option<&'a EngineInstall> {
+    match association {
+        // Version associations only ever name launcher or installed builds;
+        // source engines are associated by GUID. Several installs can share a
+        // major.minor, so take the one detection would list first.
+        EngineAssociation::Version(version) => installs
+            .iter()
+            .filter(|install| {
+                install.kind != EngineKind::Source
+                    && install
+                        .engine_version()
+                        .is_some_and(|installed| installed.matches_association(version))
+            })
+            .min_by_key(|install| std::cmp::Reverse(install.precedence())),
+        EngineAssociation::Guid(guid) => {
+            let wanted = normalize_guid(guid);
+            installs.iter().find(|install| {
+                install
+                    .guid
+                    .as_deref()
+                    .is_some_and(|guid| normalize_guid(guid) == wanted)
+            })
+        }
+        EngineAssociation::Path(path) => {
+            let key = path_key(&normalize(path));
+            installs
+                .iter()
+                .find(|install| path_key(Path::new(&install.path)) == key)
+        }
+        EngineAssociation::Native => None,
+    }
+}
+
+/// Resolves `.` and `..` lexically so relative associations compare equal to
+/// detected paths.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// The engine root a path or native association points at, for engines that
+/// were not detected. Native projects use the nearest enclosing engine root.
+pub fn association_root(
+    association: &EngineAssociation,
+    project_dir: &Path,
+    is_engine_root: impl Fn(&Path) -> bool,
+) -> Option<PathBuf> {
+    match association {
+        EngineAssociation::Path(path) => Some(normalize(path)).filter(|path| is_engine_root(path)),
+        EngineAssociation::Native => project_dir
+            .ancestors()
+            .skip(1)
+            .find(|dir| is_engine_root(dir))
+            .map(Path::to_path_buf),
+        _ => None,
+    }
+}
+
+/// End of the JSON string starting at `start` (which must be a `"`), just past
+/// its closing quote.
+fn string_end(bytes: &[u8], start: usize) -> Option<usize> {
+    let mut index = start + 1;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'\\' => index += 2,
+            b'"' => return Some(index + 1),
+            _ => index += 1,
+        }
+    }
+    None
+}
+
+/// Byte range of the value of a key in the top-level object. Keys inside
+/// nested objects such as plugin or module entries are ignored.
+fn top_level_value(contents: &str, key: &str) -> Option<Range<usize>> {
+    let bytes = contents.as_bytes();
+    let mut depth = 0usize;
+    // Whether the next string at depth 1 is a key rather than a value.
+    let mut expecting_key = false;
+    let mut index = 0;
+
+    while index < bytes.len() {
+        match bytes[index] {
+            b'"' => {
+                let end = string_end(bytes, index)?;
+                if depth == 1 && expecting_key {
+                    let name: String = serde_json::from_str(&contents[index..end]).ok()?;
+                    let mut value_start = end;
+                    while bytes
+                        .get(value_start)
+                        .is_some_and(|b| b.is_ascii_whitespace() || *b == b':')
+                    {
+                        value_start += 1;
+                    }
+                    if name == key {
+                        let value_end = if bytes.get(value_start) == Some(&b'"') {
+                            string_end(bytes, value_start)?
+                        } else {
+                            value_start
+                                + bytes[value_start..].iter().position(|b| {
+                                    matches!(b, b',' | b'}') || b.is_ascii_whitespace()
+                                })?
+                        };
+                        return Some(value_start..value_end);
+                    }
+                    expecting_key = false;
+                    index = value_start;
+                    continue;
+                }
+                index = end;
+                continue;
+            }
+            b'{' | b'[' => {
+                depth += 1;
+                expecting_key = depth == 1;
+            }
+            b'}' | b']' => depth = depth.saturating_sub(1),
+            b',' if depth == 1 => expecting_key = true,
+            _ => {}
+        }
+        index += 1;
+    }
+    None
+}
+
+/// Replaces the association in the `.uproject` text, leaving every other byte as
+/// it was. A missing field is added as the first property.
+fn replace_association(contents: &str, value: &str) -> Result<String, String> {
+    let quoted = serde_json::to_string(value)
+        .map_err(|e| format!("Failed to encode engine association: {}", e))?;
+
+    if let Some(range) = top_level_value(contents, "EngineAssociation") {
+        let mut updated = contents.to_string();
+        updated.replace_range(range, &quoted);
+        return Ok(updated);
+    }
+
+    let brace = contents
+        .find('{')
+        .ok_or_else(|| "Project file is not a JSON object".to_string())?;
+    let (head, rest) = contents.split_at(brace + 1);
+    let empty = rest.trim_start().starts_with('}');
+    let property = format!("\"EngineAssociation\": {}", quoted);
+
+    // Single-line files get the property inline; otherwise it goes on its own
+    // line with the indentation of the first existing property.
+    if !rest.starts_with(['\r', '\n']) && !empty {
+        return Ok(format!("{}{},{}", head, property, rest));
+    }
+    let newline = if contents.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let indent: String = rest
+        .trim_start_matches(['\r', '\n'])
+        .chars()
+        .take_while(|c| *c == ' ' || *c == '\t')
+        .collect();
+    let indent = if indent.is_empty() { "\t" } else { &indent };
+    if empty {
+        Ok(format!(
+            "{}{}{}{}{}{}",
+            head,
+            newline,
+            indent,
+            property,
+            newline,
+            rest.trim_start()
+        ))
+    } else {
+        Ok(format!(
+            "{}{}{}{},{}",
+            head, newline, indent, property, rest
+        ))
+    }
+}
+
+/// Writes a new association after backing the project file up next to it.
+/// Returns the backup path.
+pub fn write_association(project_path: &Path, value: &str) -> Result<PathBuf, String> {
+    let contents = fs::read_to_string(project_path)
+        .map_err(|e| format!("Failed to read project file: {}", e))?;
+    let updated = replace_association(&contents, value)?;
+    serde_json::from_str::<serde_json::Value>(&updated)
+        .map_err(|e| format!("Failed to update project file: {}", e))?;
+
+    let file_name = project_path
+        .file_name()
+        .ok_or_else(|| "Project file name is invalid".to_string())?
+        .to_string_lossy();
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S").to_string();
+    let mut backup = project_path.with_file_name(format!("{}.{}.bak", file_name, stamp));
+    // A counter keeps earlier backups made within the same second.
+    let mut counter = 1;
+    while backup.exists() {
+        backup = project_path.with_file_name(format!("{}.{}-{}.bak", file_name, stamp, counter));
+        counter += 1;
+    }
+    fs::copy(project_path, &backup)
+        .map_err(|e| format!("Failed to back up project file: {}", e))?;
+    fs::write(project_path, updated).map_err(|e| format!("Failed to write project file: {}", e))?;
+    Ok(backup)
+}
@@ -0,0 +1,366 @@
+// Health checklist for an engine install, so half-installed engines are
+// reported clearly before a build fails on them.
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EngineCheck {
+    pub id: &'static str,
+    pub title: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+    /// What to do about a warning or failure.
+    pub remediation: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EngineHealth {
+    #[serde(rename = "enginePath")]
+    pub engine_path: String,
+    /// The worst status of any check.
+    pub status: CheckStatus,
+    pub checks: Vec<EngineCheck>,
+}
+
+#[cfg(windows)]
+const HOST_PLATFORM: &str = "Win64";
+#[cfg(target_os = "macos")]
+const HOST_PLATFORM: &str = "Mac";
+#[cfg(all(not(windows), not(target_os = "macos")))]
+const HOST_PLATFORM: &str = "Linux";
+
+#[cfg(windows)]
+const EXE_SUFFIX: &str = ".exe";
+#[cfg(not(windows))]
+const EXE_SUFFIX: &str = "";
+
+fn check(
+    id: &'static str,
+    title: &'static str,
+    status: CheckStatus,
+    detail: impl Into<String>,
+    remediation: Option<&str>,
+) -> EngineCheck {
+    EngineCheck {
+        id,
+        title,
+        status,
+        detail: detail.into(),
+        remediation: if status == CheckStatus::Pass {
+            None
+        } else {
+            remediation.map(str::to_string)
+        },
+    }
+}
+
+fn first_existing(paths: &[PathBuf]) -> Option<&PathBuf> {
+    paths.iter().find(|path| path.exists())
+}
+
+fn runs_successfully(program: &str, args: &[&str]) -> bool {
+    let mut cmd = Command::new(program);
+    cmd.args(args).stdout(Stdio::null()).stderr(Stdio::null());
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+    }
+
+    cmd.status().is_ok_and(|status| status.success())
+}
+
+fn check_ubt(engine_dir: &Path, source_build: bool) -> EngineCheck {
+    let ubt = engine_dir.join("Binaries/DotNET/UnrealBuildTool/UnrealBuildTool.dll");
+    let remediation = if source_build {
+        "Run Setup and GenerateProjectFiles in the engine directory, then build the UnrealBuildTool project."
+    } else {
+        "Verify the engine in the Epic Games Launcher (Library > engine version > Verify)."
+    };
+    if ubt.is_file() {
+        check(
+            "ubt",
+            "UnrealBuildTool",
+            CheckStatus::Pass,
+            ubt.display().to_string(),
+            None,
+        )
+    } else {
+        check(
+            "ubt",
+            "UnrealBuildTool",
+            CheckStatus::Fail,
+            format!("{} is missing", ubt.display()),
+            Some(remediation),
+        )
+    }
+}
+
+fn check_editor(binaries: &Path, source_build: bool) -> EngineCheck {
+    let candidates = [
+        binaries.join(format!("UnrealEditor{}", EXE_SUFFIX)),
+        binaries.join("UnrealEditor.app"),
+        binaries.join(format!("UE4Editor{}", EXE_SUFFIX)),
+    ];
+    match first_existing(&candidates) {
+        Some(editor) => check(
+            "editor",
+            "Editor binary",
+            CheckStatus::Pass,
+            editor.display().to_string(),
+            None,
+        ),
+        None if source_build => check(
+            "editor",
+            "Editor binary",
+            CheckStatus::Warn,
+            format!("No editor binary in {}", binaries.display()),
+            Some("Build the UnrealEditor target once before launching projects with this engine."),
+        ),
+        None => check(
+            "editor",
+            "Editor binary",
+            CheckStatus::Fail,
+            format!("No editor binary in {}", binaries.display()),
+            Some("Verify the engine in the Epic Games Launcher, or reinstall it."),
+        ),
+    }
+}
+
+fn check_shader_compile_worker(binaries: &Path, source_build: bool) -> EngineCheck {
+    let worker = binaries.join(format!("ShaderCompileWorker{}", EXE_SUFFIX));
+    if worker.is_file() {
+        return check(
+            "shader-compile-worker",
+            "ShaderCompileWorker",
+            CheckStatus::Pass,
+            worker.display().to_string(),
+            None,
+        );
+    }
+    check(
+        "shader-compile-worker",
+        "ShaderCompileWorker",
+        CheckStatus::Warn,
+        format!("{} is missing", worker.display()),
+        Some(if source_build {
+            "Build the ShaderCompileWorker target (Development) so the editor can compile shaders."
+        } else {
+            "Verify the engine in the Epic Games Launcher; the editor cannot compile shaders without it."
+        }),
+    )
+}
+
+fn check_dotnet(engine_dir: &Path) -> EngineCheck {
+    if runs_successfully("dotnet", &["--list-runtimes"]) {
+        return check(
+            "dotnet",
+            ".NET runtime",
+            CheckStatus::Pass,
+            "dotnet is on PATH",
+            None,
+        );
+    }
+    // UE 5 ships its own SDK, but Stellar starts UnrealBuildTool through `dotnet` on PATH.
+    let bundled = engine_dir.join("Binaries/ThirdParty/DotNet");
+    if bundled.is_dir() {
+        return check(
+            "dotnet",
+            ".NET runtime",
+            CheckStatus::Warn,
+            format!("dotnet is not on PATH, but the engine bundles one in {}", bundled.display()),
+            Some("Add the bundled dotnet directory to PATH, or install the .NET SDK version the engine expects."),
+        );
+    }
+    check(
+        "dotnet",
+        ".NET runtime",
+        CheckStatus::Fail,
+        "dotnet could not be started",
+        Some("Install the .NET SDK required by your engine version and make sure 'dotnet' is on PATH."),
+    )
+}
+
+#[cfg(windows)]
+fn check_toolchain(_engine_dir: &Path) -> EngineCheck {
+    let program_files_x86 = std::env::var("ProgramFiles(x86)")
+        .unwrap_or_else(|_| "C:\\Program Files (x86)".to_string());
+    let vswhere =
+        PathBuf::from(&program_files_x86).join("Microsoft Visual Studio/Installer/vswhere.exe");
+    let has_msvc = vswhere.is_file() && {
+        use std::os::windows::process::CommandExt;
+        Command::new(&vswhere)
+            .args([
+                "-products",
+                "*",
+                "-requires",
+                "Microsoft.VisualStudio.Component.VC.Tools.x86.x64",
+                "-property",
+                "installationPath",
+            ])
+            .creation_flags(0x08000000) // CREATE_NO_WINDOW
+            .output()
+            .is_ok_and(|output| !output.stdout.trim_ascii().is_empty())
+    };
+    let has_sdk = PathBuf::from(&program_files_x86)
+        .join("Windows Kits/10/Include")
+        .is_dir();
+
+    match (has_msvc, has_sdk) {
+        (true, true) => check("toolchain", "Host toolchain", CheckStatus::Pass, "MSVC and the Windows SDK are installed", None),
+        (false, _) => check(
+            "toolchain",
+            "Host toolchain",
+            CheckStatus::Fail,
+            "No Visual Studio installation with the MSVC x64 tools was found",
+            Some("Install Visual Studio with the 'Game development with C++' workload and the MSVC toolset your engine expects."),
+        ),
+        (true, false) => check(
+            "toolchain",
+            "Host toolchain",
+            CheckStatus::Fail,
+            "The Windows 10/11 SDK is not installed",
+            Some("Add a Windows 10/11 SDK in the Visual Studio Installer."),
+        ),
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn check_toolchain(_engine_dir: &Path) -> EngineCheck {
+    if runs_successfully("xcode-select", &["-p"]) {
+        check(
+            "toolchain",
+            "Host toolchain",
+            CheckStatus::Pass,
+            "Xcode command line tools are installed",
+            None,
+        )
+    } else {
+        check(
+            "toolchain",
+            "Host toolchain",
+            CheckStatus::Fail,
+            "Xcode is not installed",
+            Some("Install the Xcode version your engine expects and run 'xcode-select --install'."),
+        )
+    }
+}
+
+#[cfg(all(not(windows), not(target_os = "macos")))]
+fn check_toolchain(engine_dir: &Path) -> EngineCheck {
+    if let Ok(root) = std::env::var("LINUX_MULTIARCH_ROOT") {
+        if Path::new(&root).is_dir() {
+            return check(
+                "toolchain",
+                "Host toolchain",
+                CheckStatus::Pass,
+                format!("LINUX_MULTIARCH_ROOT={}", root),
+                None,
+            );
+        }
+    }
+    let bundled = engine_dir.join("Extras/ThirdPartyNotUE/SDKs/HostLinux/Linux_x64");
+    if fs::read_dir(&bundled).is_ok_and(|mut entries| entries.next().is_some()) {
+        return check(
+            "toolchain",
+            "Host toolchain",
+            CheckStatus::Pass,
+            bundled.display().to_string(),
+            None,
+        );
+    }
+    if runs_successfully("clang++", &["--version"]) {
+        return check(
+            "toolchain",
+            "Host toolchain",
+            CheckStatus::Warn,
+            "Using the system clang; the engine's bundled toolchain was not found",
+            Some("Run Setup.sh in the engine directory, or install the cross-compile toolchain and set LINUX_MULTIARCH_ROOT."),
+        );
+    }
+    check(
+        "toolchain",
+        "Host toolchain",
+        CheckStatus::Fail,
+        "No clang toolchain was found",
+        Some("Run Setup.sh in the engine directory, or install the cross-compile toolchain and set LINUX_MULTIARCH_ROOT."),
+    )
+}
+
+fn check_writable(engine_dir: &Path, source_build: bool) -> EngineCheck {
+    // Probe the existing directory without creating anything in the install;
+    // a missing Intermediate is judged by the Engine directory that would hold it.
+    let intermediate = engine_dir.join("Intermediate");
+    let dir = if intermediate.is_dir() {
+        intermediate
+    } else {
+        engine_dir.to_path_buf()
+    };
+    let probe = dir.join(".stellar-write-test");
+    let writable = fs::write(&probe, b"ok").is_ok();
+    // Also removes a partial probe left by a failed write, e.g. on a full disk.
+    let _ = fs::remove_file(&probe);
+
+    if writable {
+        check(
+            "disk-writable",
+            "Engine directory writable",
+            CheckStatus::Pass,
+            dir.display().to_string(),
+            None,
+        )
+    } else if source_build {
+        check(
+            "disk-writable",
+            "Engine directory writable",
+            CheckStatus::Fail,
+            format!("Cannot write to {}", dir.display()),
+            Some("Source builds write to Engine/Intermediate and Engine/Binaries; fix the folder permissions or free up disk space."),
+        )
+    } else {
+        // Project builds only write to the project, so read-only launcher installs are fine.
+        check(
+            "disk-writable",
+            "Engine directory writable",
+            CheckStatus::Warn,
+            format!("Cannot write to {}", dir.display()),
+            Some("Only needed when building engine targets; project builds are unaffected."),
+        )
+    }
+}
+
+/// Runs every check against an engine root.
+pub fn check_engine(engine_root: &Path, source_build: bool) -> EngineHealth {
+    let engine_dir = engine_root.join("Engine");
+    let binaries = engine_dir.join("Binaries").join(HOST_PLATFORM);
+
+    let checks = vec![
+        check_ubt(&engine_dir, source_build),
+        check_editor(&binaries, source_build),
+        check_shader_compile_worker(&binaries, source_build),
+        check_dotnet(&engine_dir),
+        check_toolchain(&engine_dir),
+        check_writable(&engine_dir, source_build),
+    ];
+
+    EngineHealth {
+        engine_path: engine_root.to_string_lossy().to_string(),
+        status: checks
+            .iter()
+            .map(|check| check.status)
+            .max()
+            .unwrap_or(CheckStatus::Pass),
+        checks,
+    }
+}
@@ -0,0 +1,153 @@
+// Inventory of what an engine install contains: target platforms, engine
+// plugins and plugins installed from the Marketplace/Fab.
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// `Engine/Binaries` subdirectories that are not target platforms.
+const NON_PLATFORM_BINARIES: &[&str] = &["DotNET", "ThirdParty"];
+
+/// Directories inside a plugin that never contain nested plugins.
+const SKIPPED_PLUGIN_DIRS: &[&str] = &[
+    "Binaries",
+    "Config",
+    "Content",
+    "Intermediate",
+    "Resources",
+    "Shaders",
+    "Source",
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EnginePlugin {
+    /// The `.uplugin` file name, which is what projects refer to.
+    pub name: String,
+    #[serde(rename = "friendlyName")]
+    pub friendly_name: Option<String>,
+    #[serde(rename = "versionName")]
+    pub version_name: Option<String>,
+    pub category: Option<String>,
+    #[serde(rename = "enabledByDefault")]
+    pub enabled_by_default: bool,
+    #[serde(rename = "createdBy")]
+    pub created_by: Option<String>,
+    /// Directory of the plugin, relative to `Engine/Plugins`.
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EngineDetails {
+    #[serde(rename = "enginePath")]
+    pub engine_path: String,
+    /// Whether `Engine/Build/InstalledBuild.txt` exists.
+    #[serde(rename = "installedBuild")]
+    pub installed_build: bool,
+    /// Target platforms with binaries under `Engine/Binaries`, e.g. `Win64`, `Android`.
+    pub platforms: Vec<String>,
+    pub plugins: Vec<EnginePlugin>,
+    /// Plugins under `Engine/Plugins/Marketplace`.
+    #[serde(rename = "marketplacePlugins")]
+    pub marketplace_plugins: Vec<EnginePlugin>,
+}
+
+#[derive(Deserialize, Default)]
+struct PluginDescriptor {
+    #[serde(rename = "FriendlyName")]
+    friendly_name: Option<String>,
+    #[serde(rename = "VersionName")]
+    version_name: Option<String>,
+    #[serde(rename = "Category")]
+    category: Option<String>,
+    #[serde(rename = "EnabledByDefault", default)]
+    enabled_by_default: bool,
+    #[serde(rename = "CreatedBy")]
+    created_by: Option<String>,
+}
+
+fn list_platforms(engine_dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(engine_dir.join("Binaries")) else {
+        return Vec::new();
+    };
+    let mut platforms: Vec<String> = entries
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| !NON_PLATFORM_BINARIES.contains(&name.as_str()))
+        .collect();
+    platforms.sort();
+    platforms
+}
+
+/// Finds `.uplugin` files, not descending into a plugin once its descriptor is found.
+fn find_plugins(dir: &Path, skip: Option<&Path>, found: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut subdirs = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            subdirs.push(path);
+        } else if path.extension().is_some_and(|ext| ext == "uplugin") {
+            found.push(path);
+            return;
+        }
+    }
+    for subdir in subdirs {
+        let name = subdir.file_name().unwrap_or_default().to_string_lossy();
+        if SKIPPED_PLUGIN_DIRS.contains(&name.as_ref()) || Some(subdir.as_path()) == skip {
+            continue;
+        }
+        find_plugins(&subdir, skip, found);
+    }
+}
+
+/// Descriptors that fail to parse are still listed, with only their name.
+fn read_plugin(descriptor: &Path, plugins_dir: &Path) -> EnginePlugin {
+    let parsed: PluginDescriptor = fs::read_to_string(descriptor)
+        .ok()
+        .and_then(|contents| serde_json::from_str(contents.trim_start_matches('\u{feff}')).ok())
+        .unwrap_or_default();
+    let dir = descriptor.parent().unwrap_or(plugins_dir);
+    EnginePlugin {
+        name: descriptor
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        friendly_name: parsed.friendly_name,
+        version_name: parsed.version_name,
+        category: parsed.category,
+        enabled_by_default: parsed.enabled_by_default,
+        created_by: parsed.created_by,
+        path: dir
+            .strip_prefix(plugins_dir)
+            .unwrap_or(dir)
+            .to_string_lossy()
+            .replace('\\', "/"),
+    }
+}
+
+fn list_plugins(dir: &Path, skip: Option<&Path>, plugins_dir: &Path) -> Vec<EnginePlugin> {
+    let mut descriptors = Vec::new();
+    find_plugins(dir, skip, &mut descriptors);
+    let mut plugins: Vec<EnginePlugin> = descriptors
+        .iter()
+        .map(|descriptor| read_plugin(descriptor, plugins_dir))
+        .collect();
+    plugins.sort_by_key(|plugin| plugin.name.to_lowercase());
+    plugins
+}
+
+pub fn engine_details(engine_root: &Path) -> EngineDetails {
+    let engine_dir = engine_root.join("Engine");
+    let plugins_dir = engine_dir.join("Plugins");
+    let marketplace_dir = plugins_dir.join("Marketplace");
+
+    EngineDetails {
+        engine_path: engine_root.to_string_lossy().to_string(),
+        installed_build: engine_dir.join("Build/InstalledBuild.txt").is_file(),
+        platforms: list_platforms(&engine_dir),
+        plugins: list_plugins(&plugins_dir, Some(&marketplace_dir), &plugins_dir),
+        marketplace_plugins: list_plugins(&marketplace_dir, None, &plugins_dir),
+    }
+}
@@ -0,0 +1,283 @@
+// The per-user list of registered engines that Unreal keeps for source builds:
+// `HKCU\Software\Epic Games\Unreal Engine\Builds` on Windows and the
+// `[Installations]` section of `Epic/UnrealEngine/Install.ini` elsewhere.
+use std::path::{Path, PathBuf};
+
+#[cfg(not(windows))]
+use std::fs;
+
+#[cfg(windows)]
+const REGISTRY_KEY: &str = r"HKCU\Software\Epic Games\Unreal Engine\Builds";
+
+#[cfg(not(windows))]
+const INSTALLATIONS_SECTION: &str = "[Installations]";
+
+/// A registered engine: its GUID (with braces, as Unreal writes it) and root directory.
+#[derive(Debug, Clone)]
+pub struct RegisteredEngine {
+    pub guid: String,
+    pub path: PathBuf,
+}
+
+/// New registrations use Unreal's own format, e.g. `{8A3E6AA4-...}`.
+pub fn new_guid() -> String {
+    format!("{{{}}}", uuid::Uuid::new_v4().to_string().to_uppercase())
+}
+
+/// Comparable form of an engine path: forward slashes, no trailing slash, and
+/// case-insensitive on Windows.
+pub fn path_key(path: &Path) -> String {
+    let key = path
+        .to_string_lossy()
+        .replace('\\', "/")
+        .trim_end_matches('/')
+        .to_string();
+    if cfg!(windows) {
+        key.to_lowercase()
+    } else {
+        key
+    }
+}
+
+#[cfg(not(windows))]
+pub fn install_ini_path() -> Option<PathBuf> {
+    let home = PathBuf::from(std::env::var("HOME").ok()?);
+    #[cfg(target_os = "macos")]
+    let base = home.join("Library/Application Support");
+    #[cfg(not(target_os = "macos"))]
+    let base = std::env::var("XDG_CONFIG_HOME")
+        .ok()
+        .filter(|dir| !dir.is_empty())
+        .map_or_else(|| home.join(".config"), PathBuf::from);
+    Some(base.join("Epic/UnrealEngine/Install.ini"))
+}
+
+/// Splits an ini file into the lines before `[Installations]`, its header, the
+/// lines inside it and the lines after it.
+#[cfg(not(windows))]
+fn split_installations(contents: &str) -> (Vec<&str>, Option<&str>, Vec<&str>, Vec<&str>) {
+    let lines: Vec<&str> = contents.lines().collect();
+    let Some(start) = lines
+        .iter()
+        .position(|line| line.trim().eq_ignore_ascii_case(INSTALLATIONS_SECTION))
+    else {
+        return (lines, None, Vec::new(), Vec::new());
+    };
+    let end = lines[start + 1..]
+        .iter()
+        .position(|line| line.trim_start().starts_with('['))
+        .map_or(lines.len(), |offset| start + 1 + offset);
+    (
+        lines[..start].to_vec(),
+        Some(lines[start]),
+        lines[start + 1..end].to_vec(),
+        lines[end..].to_vec(),
+    )
+}
+
+#[cfg(not(windows))]
+fn parse_entry(line: &str) -> Option<RegisteredEngine> {
+    let (guid, path) = line.split_once('=')?;
+    let (guid, path) = (guid.trim(), path.trim());
+    if guid.is_empty() || path.is_empty() || guid.starts_with(';') {
+        return None;
+    }
+    Some(RegisteredEngine {
+        guid: guid.to_string(),
+        path: PathBuf::from(path),
+    })
+}
+
+#[cfg(not(windows))]
+pub fn registered_engines() -> Vec<RegisteredEngine> {
+    let Some(contents) = install_ini_path().and_then(|path| fs::read_to_string(path).ok()) else {
+        return Vec::new();
+    };
+    let (_, _, entries, _) = split_installations(&contents);
+    entries.into_iter().filter_map(parse_entry).collect()
+}
+
+/// Rewrites the entries of the `[Installations]` section. Comments, blank lines
+/// and the rest of the file are kept, as is the file's line ending.
+#[cfg(not(windows))]
+fn write_installations(update: impl FnOnce(&mut Vec<RegisteredEngine>)) -> Result<(), String> {
+    let path = install_ini_path().ok_or_else(|| "Could not locate Install.ini".to_string())?;
+    let contents = fs::read_to_string(&path).unwrap_or_default();
+    let newline = if contents.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let (before, header, section, after) = split_installations(&contents);
+
+    let mut engines: Vec<RegisteredEngine> = section
+        .iter()
+        .filter_map(|line| parse_entry(line))
+        .collect();
+    update(&mut engines);
+    let entry =
+        |engine: &RegisteredEngine| format!("{}={}", engine.guid, engine.path.to_string_lossy());
+
+    // Entries that remain are rewritten where they were; new ones go after the
+    // last entry, or after the last non-blank line when there were none.
+    let mut body: Vec<String> = Vec::new();
+    let mut written = Vec::new();
+    let mut insert_at = None;
+    for line in &section {
+        match parse_entry(line) {
+            Some(existing) => {
+                if let Some(engine) = engines
+                    .iter()
+                    .find(|engine| engine.guid.eq_ignore_ascii_case(&existing.guid))
+                    .filter(|engine| !written.contains(&engine.guid))
+                {
+                    body.push(entry(engine));
+                    written.push(engine.guid.clone());
+                }
+                insert_at = Some(body.len());
+            }
+            None => body.push(line.to_string()),
+        }
+    }
+    let insert_at = insert_at.unwrap_or_else(|| {
+        body.iter()
+            .rposition(|line| !line.trim().is_empty())
+            .map_or(0, |index| index + 1)
+    });
+    let added: Vec<String> = engines
+        .iter()
+        .filter(|engine| !written.contains(&engine.guid))
+        .map(entry)
+        .collect();
+    body.splice(insert_at..insert_at, added);
+
+    let mut lines: Vec<String> = before.iter().map(|line| line.to_string()).collect();
+    if header.is_none() && lines.last().is_some_and(|line| !line.trim().is_empty()) {
+        lines.push(String::new());
+    }
+    lines.push(header.unwrap_or(INSTALLATIONS_SECTION).to_string());
+    lines.extend(body);
+    lines.extend(after.iter().map(|line| line.to_string()));
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create engine registry directory: {}", e))?;
+    }
+    fs::write(&path, lines.join(newline) + newline)
+        .map_err(|e| format!("Failed to write Install.ini: {}", e))
+}
+
+#[cfg(windows)]
+fn reg_command() -> std::process::Command {
+    use std::os::windows::process::CommandExt;
+    let mut cmd = std::process::Command::new("reg");
+    cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+    cmd
+}
+
+#[cfg(windows)]
+pub fn registered_engines() -> Vec<RegisteredEngine> {
+    let Ok(output) = reg_command().args(["query", REGISTRY_KEY]).output() else {
+        return Vec::new();
+    };
+    // Each value is listed as `    {GUID}    REG_SZ    D:/UnrealEngine`.
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (guid, path) = line.trim().split_once("REG_SZ")?;
+            let (guid, path) = (guid.trim(), path.trim());
+            (!guid.is_empty() && !path.is_empty()).then(|| RegisteredEngine {
+                guid: guid.to_string(),
+                path: PathBuf::from(path),
+            })
+        })
+        .collect()
+}
+
+#[cfg(windows)]
+fn run_reg(args: &[&str]) -> Result<(), String> {
+    let output = reg_command()
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run reg: {}", e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Failed to update the engine registry: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+/// Registers `path` and returns its GUID. A path that is already registered
+/// keeps its existing GUID unless a different one is requested.
+pub fn register(path: &Path, guid: Option<String>) -> Result<String, String> {
+    let key = path_key(path);
+    let existing = registered_engines()
+        .into_iter()
+        .find(|engine| path_key(&engine.path) == key);
+    if let (Some(existing), None) = (&existing, &guid) {
+        return Ok(existing.guid.clone());
+    }
+    if guid
+        .as_deref()
+        .is_some_and(|guid| guid.trim().is_empty() || guid.contains(['=', '\n', '\r']))
+    {
+        return Err("Invalid engine GUID".to_string());
+    }
+    let guid = guid.map_or_else(new_guid, |guid| guid.trim().to_string());
+
+    #[cfg(windows)]
+    {
+        if let Some(existing) = existing.filter(|engine| engine.guid != guid) {
+            run_reg(&["delete", REGISTRY_KEY, "/v", &existing.guid, "/f"])?;
+        }
+        let value = path.to_string_lossy().replace('\\', "/");
+        run_reg(&[
+            "add",
+            REGISTRY_KEY,
+            "/v",
+            &guid,
+            "/t",
+            "REG_SZ",
+            "/d",
+            &value,
+            "/f",
+        ])?;
+    }
+
+    #[cfg(not(windows))]
+    {
+        let _ = existing;
+        write_installations(|engines| {
+            engines.retain(|engine| {
+                path_key(&engine.path) != key && !engine.guid.eq_ignore_ascii_case(&guid)
+            });
+            engines.push(RegisteredEngine {
+                guid: guid.clone(),
+                path: path.to_path_buf(),
+            });
+        })?;
+    }
+
+    Ok(guid)
+}
+
+/// Removes a registration. Returns `false` when the GUID was not registered.
+pub fn unregister(guid: &str) -> Result<bool, String> {
+    let Some(engine) = registered_engines()
+        .into_iter()
+        .find(|engine| engine.guid.eq_ignore_ascii_case(guid))
+    else {
+        return Ok(false);
+    };
+
+    #[cfg(windows)]
+    run_reg(&["delete", REGISTRY_KEY, "/v", &engine.guid, "/f"])?;
+
+    #[cfg(not(windows))]
+    write_installations(|engines| engines.retain(|e| !e.guid.eq_ignore_ascii_case(&engine.guid)))?;
+
+    Ok(true)
+}
@@ -0,0 +1,268 @@
+// Engine version stamps read from `Engine/Build/Build.version` and target
+// receipts, and the comparable version used to order and match engines.
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Engine version stamp, as written to receipts and `Engine/Build/Build.version`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildVersion {
+    #[serde(rename = "majorVersion", alias = "MajorVersion", default)]
+    pub major_version: u32,
+    #[serde(rename = "minorVersion", alias = "MinorVersion", default)]
+    pub minor_version: u32,
+    #[serde(rename = "patchVersion", alias = "PatchVersion", default)]
+    pub patch_version: u32,
+    #[serde(rename = "changelist", alias = "Changelist", default)]
+    pub changelist: u64,
+    #[serde(
+        rename = "compatibleChangelist",
+        alias = "CompatibleChangelist",
+        default
+    )]
+    pub compatible_changelist: u64,
+    #[serde(rename = "branchName", alias = "BranchName", default)]
+    pub branch_name: String,
+    #[serde(
+        rename = "isLicenseeVersion",
+        alias = "IsLicenseeVersion",
+        default,
+        deserialize_with = "deserialize_flag"
+    )]
+    pub is_licensee_version: bool,
+}
+
+/// UE writes boolean flags as `0`/`1`.
+fn deserialize_flag<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+    Ok(match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::Bool(value) => value,
+        serde_json::Value::Number(value) => value.as_u64().unwrap_or(0) != 0,
+        _ => false,
+    })
+}
+
+impl BuildVersion {
+    /// `Major.Minor.Patch`, the form used for labels and sorting.
+    pub fn short(&self) -> String {
+        format!(
+            "{}.{}.{}",
+            self.major_version, self.minor_version, self.patch_version
+        )
+    }
+}
+
+/// Reads `Engine/Build/Build.version` under an engine root.
+pub fn read_build_version(engine_root: &Path) -> Option<BuildVersion> {
+    let contents = fs::read_to_string(engine_root.join("Engine/Build/Build.version")).ok()?;
+    let version: BuildVersion = serde_json::from_str(&contents).ok()?;
+    // A zero major version means the file did not carry a real stamp.
+    (version.major_version > 0).then_some(version)
+}
+
+/// Release stage of an engine version; previews sort before the release.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ReleaseStage {
+    Preview(u32),
+    Release,
+}
+
+/// A comparable engine version. Orders by `major.minor.patch` (hotfixes are
+/// patches), then previews before the release, then by changelist so two
+/// source builds of the same version sort by how recent they are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct EngineVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+    pub stage: ReleaseStage,
+    pub changelist: u64,
+}
+
+fn version_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"^\s*(\d+)(?:\.(\d+))?(?:\.(\d+))?(.*)$").expect("valid version regex")
+    })
+}
+
+fn preview_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"(?i)(?:preview|early[\s_-]*access)(?:[\s_-]*(\d+))?")
+            .expect("valid preview regex")
+    })
+}
+
+fn changelist_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    // Changelists are long numbers set off by `-` or `+`, as in the launcher's
+    // `5.3.2-29314046+++UE5+Release-5.3`.
+    RE.get_or_init(|| Regex::new(r"(?:^|[-+])(\d{5,})").expect("valid changelist regex"))
+}
+
+impl EngineVersion {
+    /// Parses strings such as `5.3`, `5.3.2`, `5.4.0-preview-1`, `5.4 Preview 2`
+    /// and `5.3.2-29314046+++UE5+Release-5.3`.
+    pub fn parse(value: &str) -> Option<Self> {
+        let caps = version_regex().captures(value)?;
+        let number = |index: usize| {
+            caps.get(index)
+                .and_then(|m| m.as_str().parse::<u32>().ok())
+                .unwrap_or(0)
+        };
+        let rest = caps.get(4).map_or("", |m| m.as_str());
+        let stage = preview_regex()
+            .captures(rest)
+            .map_or(ReleaseStage::Release, |preview| {
+                ReleaseStage::Preview(
+                    preview
+                        .get(1)
+                        .and_then(|m| m.as_str().parse().ok())
+                        .unwrap_or(0),
+                )
+            });
+        let changelist = changelist_regex()
+            .captures(rest)
+            .and_then(|cl| cl[1].parse().ok())
+            .unwrap_or(0);
+        Some(EngineVersion {
+            major: caps[1].parse().ok()?,
+            minor: number(2),
+            patch: number(3),
+            stage,
+            changelist,
+        })
+    }
+
+    /// Combines a version string with an engine's `Build.version`. The stamp is
+    /// authoritative for the numbers; only the string can say it is a preview.
+    pub fn resolve(version: Option<&str>, build: Option<&BuildVersion>) -> Option<Self> {
+        let parsed = version.and_then(EngineVersion::parse);
+        let Some(build) = build else {
+            return parsed;
+        };
+        let stamped_changelist = if build.changelist != 0 {
+            build.changelist
+        } else {
+            build.compatible_changelist
+        };
+        Some(EngineVersion {
+            major: build.major_version,
+            minor: build.minor_version,
+            patch: build.patch_version,
+            stage: parsed.map_or(ReleaseStage::Release, |parsed| parsed.stage),
+            changelist: if stamped_changelist != 0 {
+                stamped_changelist
+            } else {
+                parsed.map_or(0, |parsed| parsed.changelist)
+            },
+        })
+    }
+
+    /// The version without its changelist, so callers can rank other criteria
+    /// between the release and the changelist tie-breaker.
+    pub fn release(&self) -> EngineVersion {
+        EngineVersion {
+            changelist: 0,
+            ..*self
+        }
+    }
+
+    /// Whether a launcher-style association such as `5.3` names this version.
+    /// Only the components the association spells out are compared; a bare
+    /// major version means `major.0`.
+    pub fn matches_association(&self, association: &str) -> bool {
+        let parts: Vec<u32> = association
+            .trim()
+            .split('.')
+            .map_while(|part| part.parse().ok())
+            .collect();
+        match parts.as_slice() {
+            [] => false,
+            [major] => self.major == *major && self.minor == 0,
+            [major, minor] => self.major == *major && self.minor == *minor,
+            [major, minor, patch, ..] => {
+                self.major == *major && self.minor == *minor && self.patch == *patch
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(value: &str) -> EngineVersion {
+        EngineVersion::parse(value).expect("parses")
+    }
+
+    #[test]
+    fn parses_releases_previews_and_changelists() {
+        assert_eq!(
+            version("5.3"),
+            EngineVersion {
+                major: 5,
+                minor: 3,
+                patch: 0,
+                stage: ReleaseStage::Release,
+                changelist: 0,
+            }
+        );
+        assert_eq!(version("5.4.0-preview-1").stage, ReleaseStage::Preview(1));
+        assert_eq!(version("5.4 Preview 2").stage, ReleaseStage::Preview(2));
+        let launcher = version("5.3.2-29314046+++UE5+Release-5.3");
+        assert_eq!((launcher.patch, launcher.changelist), (2, 29314046));
+        assert_eq!(launcher.stage, ReleaseStage::Release);
+        assert_eq!(EngineVersion::parse("Custom"), None);
+    }
+
+    #[test]
+    fn resolve_prefers_the_build_stamp() {
+        let build = BuildVersion {
+            major_version: 5,
+            minor_version: 4,
+            patch_version: 1,
+            compatible_changelist: 33043543,
+            ..Default::default()
+        };
+        let resolved = EngineVersion::resolve(Some("5.4 Preview 1"), Some(&build)).unwrap();
+        assert_eq!(resolved.release(), version("5.4.1-preview-1"));
+        assert_eq!(resolved.changelist, 33043543);
+
+        let stamped = BuildVersion {
+            changelist: 33100000,
+            ..build.clone()
+        };
+        let resolved = EngineVersion::resolve(None, Some(&stamped)).unwrap();
+        assert_eq!(resolved.stage, ReleaseStage::Release);
+        assert_eq!(resolved.changelist, 33100000);
+
+        assert_eq!(
+            EngineVersion::resolve(Some("5.2"), None),
+            Some(version("5.2"))
+        );
+        assert_eq!(EngineVersion::resolve(None, None), None);
+    }
+
+    #[test]
+    fn orders_hotfixes_previews_and_changelists() {
+        assert!(version("5.4.0-preview-1") < version("5.4.0"));
+        assert!(version("5.4 Preview 1") < version("5.4 Preview 2"));
+        assert!(version("5.3.2") > version("5.3.1"));
+        assert!(version("5.10") > version("5.4.0"));
+        assert!(version("5.3.0-29314046") > version("5.3.0-29000000"));
+        assert!(version("5.3.1") > version("5.3.0-29314046"));
+    }
+
+    #[test]
+    fn matches_only_the_spelled_out_components() {
+        let installed = version("5.3.2");
+        assert!(installed.matches_association("5.3"));
+        assert!(installed.matches_association("5.3.2"));
+        assert!(!installed.matches_association("5.3.1"));
+        assert!(!installed.matches_association("5"));
+        assert!(!installed.matches_association(""));
+    }
+}
@@ -0,0 +1,73 @@
+// Change tracking for detected engines. The watched locations are polled
+// rather than subscribed to, because they are few and several of them (the
+// launcher manifest, Install.ini) are rewritten in place by other programs.
+use crate::EngineInstall;
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+pub const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Payload of the `engines-changed` event.
+#[derive(Debug, Clone, Serialize)]
+pub struct EnginesChanged {
+    pub added: Vec<EngineInstall>,
+    pub removed: Vec<EngineInstall>,
+    /// Engines whose details changed, such as a new `Build.version` after a hotfix.
+    pub updated: Vec<EngineInstall>,
+}
+
+impl EnginesChanged {
+    pub fn between(previous: &[EngineInstall], current: &[EngineInstall]) -> Self {
+        let find = |installs: &[EngineInstall], id: &str| {
+            installs.iter().find(|install| install.id == id).cloned()
+        };
+        EnginesChanged {
+            added: current
+                .iter()
+                .filter(|install| find(previous, &install.id).is_none())
+                .cloned()
+                .collect(),
+            removed: previous
+                .iter()
+                .filter(|install| find(current, &install.id).is_none())
+                .cloned()
+                .collect(),
+            updated: current
+                .iter()
+                .filter(|install| {
+                    find(previous, &install.id).is_some_and(|before| before != **install)
+                })
+                .cloned()
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.updated.is_empty()
+    }
+}
+
+/// Modification time and size of each watched path. A directory's modification
+/// time changes when entries are added to or removed from it.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Fingerprint(Vec<(PathBuf, Option<(SystemTime, u64)>)>);
+
+impl Fingerprint {
+    pub fn of(mut paths: Vec<PathBuf>) -> Self {
+        paths.sort();
+        paths.dedup();
+        Fingerprint(
+            paths
+                .into_iter()
+                .map(|path| {
+                    let stamp = fs::metadata(&path)
+                        .ok()
+                        .and_then(|metadata| Some((metadata.modified().ok()?, metadata.len())));
+                    (path, stamp)
+                })
+                .collect(),
+        )
+    }
+}
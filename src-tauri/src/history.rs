@@ -0,0 +1,262 @@
+// Persistent build history stored as JSON in the app data directory.
+use crate::build_log::BuildSummary;
+use crate::receipt::BuildProducts;
+use crate::retry::BuildAttempt;
+use crate::timing::TimingProfile;
+use crate::trends::DurationRegression;
+use crate::BuildStatus;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+
+/// Most builds kept in history; the oldest finished builds are dropped first.
+const MAX_RECORDS: usize = 500;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildRecord {
+    pub id: String,
+    #[serde(rename = "projectPath")]
+    pub project_path: String,
+    #[serde(rename = "projectName")]
+    pub project_name: String,
+    #[serde(rename = "enginePath")]
+    pub engine_path: String,
+    #[serde(rename = "engineVersion")]
+    pub engine_version: Option<String>,
+    pub target: String,
+    pub platform: String,
+    pub configuration: String,
+    pub arguments: Vec<String>,
+    #[serde(flatten)]
+    pub status: BuildStatus,
+    #[serde(rename = "durationMs")]
+    pub duration_ms: Option<i64>,
+    pub summary: Option<BuildSummary>,
+    #[serde(default, rename = "logPath")]
+    pub log_path: Option<String>,
+    /// Set when the build was notably slower than its rolling baseline.
+    #[serde(default)]
+    pub regression: Option<DurationRegression>,
+    /// Compile-time breakdown, present when the build ran with `-Timing`.
+    #[serde(default)]
+    pub timing: Option<TimingProfile>,
+    /// Files listed in the target receipt of a successful build.
+    #[serde(default)]
+    pub products: Option<BuildProducts>,
+    /// Every UBT run of a build that was retried, oldest first. Empty when the
+    /// build ran once.
+    #[serde(default)]
+    pub attempts: Vec<BuildAttempt>,
+    /// Time spent waiting between retries; not part of `duration_ms`.
+    #[serde(default, rename = "retryBackoffMs")]
+    pub retry_backoff_ms: Option<i64>,
+}
+
+impl BuildRecord {
+    pub fn started_at(&self) -> Option<DateTime<Utc>> {
+        parse_timestamp(self.status.started_at.as_deref()?)
+    }
+
+    /// Fills in the duration once both timestamps are known. Retry backoff is
+    /// left out so retried builds do not skew trends and regression checks.
+    pub fn update_duration(&mut self) {
+        let finished = self.status.finished_at.as_deref().and_then(parse_timestamp);
+        if let (Some(started), Some(finished)) = (self.started_at(), finished) {
+            let elapsed = (finished - started).num_milliseconds();
+            self.duration_ms = Some((elapsed - self.retry_backoff_ms.unwrap_or(0)).max(0));
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BuildFilter {
+    pub project_path: Option<String>,
+    pub status: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub limit: Option<usize>,
+}
+
+impl BuildFilter {
+    fn matches(&self, record: &BuildRecord) -> bool {
+        if let Some(project) = &self.project_path {
+            if !record.project_path.eq_ignore_ascii_case(project) {
+                return false;
+            }
+        }
+        if let Some(status) = &self.status {
+            if record.status.status != *status {
+                return false;
+            }
+        }
+        if self.since.is_some() || self.until.is_some() {
+            let Some(started) = record.started_at() else {
+                return false;
+            };
+            if self.since.is_some_and(|since| started < since) {
+                return false;
+            }
+            if self.until.is_some_and(|until| started > until) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+pub fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+pub struct BuildHistory {
+    records: Vec<BuildRecord>,
+    /// Snapshots to write; a background thread saves them so callers holding
+    /// the history lock never wait on the disk.
+    saver: Sender<Vec<BuildRecord>>,
+}
+
+impl BuildHistory {
+    /// Loads history from disk. Builds that were still running when Stellar
+    /// last exited are marked as failed, since their outcome is unknown.
+    pub fn load(path: PathBuf) -> Self {
+        let mut records = read_records(&path);
+
+        let mut interrupted = false;
+        for record in records.iter_mut() {
+            if record.status.status == "running" {
+                record.status.status = "error".to_string();
+                record.status.error = Some("Stellar exited before the build finished".to_string());
+                interrupted = true;
+            }
+        }
+
+        let (saver, snapshots) = mpsc::channel();
+        std::thread::spawn(move || save_snapshots(&path, snapshots));
+        let mut history = BuildHistory { records, saver };
+        if interrupted || history.prune() {
+            history.save();
+        }
+        history
+    }
+
+    pub fn get(&self, build_id: &str) -> Option<&BuildRecord> {
+        self.records.iter().find(|record| record.id == build_id)
+    }
+
+    /// Returns matching records, newest first.
+    pub fn query(&self, filter: &BuildFilter) -> Vec<BuildRecord> {
+        let mut records: Vec<BuildRecord> = self
+            .records
+            .iter()
+            .filter(|record| filter.matches(record))
+            .cloned()
+            .collect();
+        records.sort_by_key(|record| std::cmp::Reverse(record.started_at()));
+        if let Some(limit) = filter.limit {
+            records.truncate(limit);
+        }
+        records
+    }
+
+    /// Stores the record and queues the history to be written to disk.
+    pub fn upsert(&mut self, record: BuildRecord) {
+        match self.records.iter_mut().find(|r| r.id == record.id) {
+            Some(existing) => *existing = record,
+            None => self.records.push(record),
+        }
+        self.prune();
+        self.save();
+    }
+
+    /// Drops the oldest finished builds beyond [`MAX_RECORDS`]. Returns whether
+    /// anything was removed.
+    fn prune(&mut self) -> bool {
+        let finished = self
+            .records
+            .iter()
+            .filter(|record| record.status.status != "running")
+            .count();
+        let excess = self.records.len().saturating_sub(MAX_RECORDS).min(finished);
+        if excess == 0 {
+            return false;
+        }
+        let mut oldest: Vec<(Option<DateTime<Utc>>, String)> = self
+            .records
+            .iter()
+            .filter(|record| record.status.status != "running")
+            .map(|record| (record.started_at(), record.id.clone()))
+            .collect();
+        oldest.sort();
+        oldest.truncate(excess);
+        self.records
+            .retain(|record| !oldest.iter().any(|(_, id)| *id == record.id));
+        true
+    }
+
+    fn save(&self) {
+        let _ = self.saver.send(self.records.clone());
+    }
+}
+
+/// Reads the history file. A file that cannot be read or parsed is moved aside
+/// rather than overwritten by the next save, so it can still be recovered.
+fn read_records(path: &Path) -> Vec<BuildRecord> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Vec::new(),
+        Err(e) => {
+            set_aside(path, &format!("Failed to read build history: {}", e));
+            return Vec::new();
+        }
+    };
+    serde_json::from_str(&contents).unwrap_or_else(|e| {
+        set_aside(path, &format!("Failed to parse build history: {}", e));
+        Vec::new()
+    })
+}
+
+fn set_aside(path: &Path, reason: &str) {
+    let mut aside = path.as_os_str().to_owned();
+    aside.push(format!(
+        ".{}.bad",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    let aside = PathBuf::from(aside);
+    match fs::rename(path, &aside) {
+        Ok(()) => eprintln!("{}; moved it to {:?}", reason, aside),
+        Err(e) => eprintln!("{}; could not move it aside: {}", reason, e),
+    }
+}
+
+/// Writes queued snapshots until the history is dropped. Only the newest
+/// pending snapshot is written when several arrive while one is being saved.
+fn save_snapshots(path: &Path, snapshots: Receiver<Vec<BuildRecord>>) {
+    while let Ok(mut records) = snapshots.recv() {
+        while let Ok(newer) = snapshots.try_recv() {
+            records = newer;
+        }
+        if let Err(e) = write_records(path, &records) {
+            eprintln!("{}", e);
+        }
+    }
+}
+
+fn write_records(path: &Path, records: &[BuildRecord]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create history directory: {}", e))?;
+    }
+
+    let contents = serde_json::to_string_pretty(records)
+        .map_err(|e| format!("Failed to serialize build history: {}", e))?;
+
+    // Write to a temporary file first so a crash never leaves a truncated history.
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, contents).map_err(|e| format!("Failed to write build history: {}", e))?;
+    fs::rename(&tmp_path, path).map_err(|e| format!("Failed to write build history: {}", e))
+}
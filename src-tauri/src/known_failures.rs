@@ -0,0 +1,155 @@
+// Known build failures: log patterns mapped to explanations and suggested fixes.
+use regex::{RegexSet, RegexSetBuilder};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnownFailureRule {
+    pub id: String,
+    pub title: String,
+    /// Regular expression matched case-insensitively against each log line.
+    pub pattern: String,
+    pub explanation: String,
+    pub fix: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RuleFile {
+    rules: Vec<KnownFailureRule>,
+}
+
+/// A rule that matched at least one line of a build.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FailureHint {
+    #[serde(rename = "ruleId")]
+    pub rule_id: String,
+    pub title: String,
+    pub explanation: String,
+    pub fix: String,
+    /// First matching line and its sequence number in the build log.
+    #[serde(rename = "firstLine")]
+    pub first_line: String,
+    #[serde(rename = "firstSeq")]
+    pub first_seq: usize,
+    pub occurrences: usize,
+}
+
+fn rule(id: &str, title: &str, pattern: &str, explanation: &str, fix: &str) -> KnownFailureRule {
+    KnownFailureRule {
+        id: id.to_string(),
+        title: title.to_string(),
+        pattern: pattern.to_string(),
+        explanation: explanation.to_string(),
+        fix: fix.to_string(),
+    }
+}
+
+pub fn builtin_rules() -> Vec<KnownFailureRule> {
+    vec![
+        rule(
+            "ubt-mutex",
+            "UnrealBuildTool is already running",
+            r"conflicting instance of UnrealBuildTool|(?:another|an) instance of UnrealBuildTool is already running|Waiting for '?UnrealBuildTool'? mutex",
+            "Another UnrealBuildTool process holds the global build mutex, usually an editor-triggered build, an IDE build or a stuck UBT from an earlier run.",
+            "Wait for the other build to finish, or end any leftover UnrealBuildTool/dotnet processes in Task Manager and build again.",
+        ),
+        rule(
+            "live-coding-active",
+            "Live Coding is active",
+            r"Unable to build while Live Coding is active",
+            "The editor has Live Coding enabled for this project, which locks the module DLLs UBT needs to replace.",
+            "Close the Unreal Editor, or press Ctrl+Alt+F11 in the editor to compile through Live Coding instead.",
+        ),
+        rule(
+            "platform-sdk-missing",
+            "Platform SDK not found",
+            r"Platform \S+ is not a valid platform to build|is not installed properly|Windows SDK .*(?:not found|could not be found)|Unable to find (?:installation of|valid) .*SDK|No valid Visual C\+\+ toolchain|Visual Studio .* must be installed",
+            "UBT could not find the compiler toolchain or platform SDK required for the selected platform.",
+            "Install the Visual Studio workload 'Game development with C++' including a Windows 10/11 SDK and the MSVC toolset your engine version expects, then regenerate project files.",
+        ),
+        rule(
+            "dotnet-missing",
+            ".NET runtime not found",
+            r"'dotnet' is not recognized|dotnet: command not found|No such file or directory.*dotnet|It was not possible to find any (?:compatible framework version|installed \.NET)|You must install or update \.NET|The library 'hostfxr' .* was not found",
+            "UnrealBuildTool runs on .NET and Stellar could not start a suitable dotnet runtime.",
+            "Install the .NET SDK version required by your engine (UE 5.x ships one under Engine/Binaries/ThirdParty/DotNet) and make sure 'dotnet' is on PATH.",
+        ),
+        rule(
+            "out-of-disk",
+            "Out of disk space",
+            r"LNK1180|LNK1201|C1085|C1083.*No space|insufficient disk space|not enough space on the disk|No space left on device",
+            "The compiler or linker ran out of disk space, which is most common while writing large PDBs and intermediate files during linking.",
+            "Free space on the drive holding the project's Intermediate and Binaries folders (and the temp directory), then rebuild.",
+        ),
+    ]
+}
+
+/// Explanation and fix for a build whose process could not be started. A missing
+/// `dotnet` never produces log output, so no log rule can catch it.
+pub fn spawn_failure_hint(error: &io::Error) -> Option<String> {
+    if error.kind() != io::ErrorKind::NotFound {
+        return None;
+    }
+    builtin_rules()
+        .into_iter()
+        .find(|rule| rule.id == "dotnet-missing")
+        .map(|rule| format!("{} {}", rule.explanation, rule.fix))
+}
+
+/// A compiled set of rules, built-ins first and team rules overriding by id.
+pub struct KnownFailureRules {
+    rules: Vec<KnownFailureRule>,
+    set: RegexSet,
+}
+
+impl KnownFailureRules {
+    /// Loads the built-in rules plus every readable rule file. Problems with
+    /// team rules are returned as warnings instead of failing the build.
+    pub fn load(rule_files: &[&Path]) -> (Self, Vec<String>) {
+        let mut rules = builtin_rules();
+        let mut warnings = Vec::new();
+
+        for path in rule_files {
+            let contents = match fs::read_to_string(path) {
+                Ok(contents) => contents,
+                Err(_) => continue,
+            };
+            let file: RuleFile = match serde_json::from_str(&contents) {
+                Ok(file) => file,
+                Err(e) => {
+                    warnings.push(format!("Ignoring known-failure rules in {:?}: {}", path, e));
+                    continue;
+                }
+            };
+            for custom in file.rules {
+                rules.retain(|existing| existing.id != custom.id);
+                rules.push(custom);
+            }
+        }
+
+        rules.retain(|rule| match regex::Regex::new(&rule.pattern) {
+            Ok(_) => true,
+            Err(e) => {
+                warnings.push(format!("Ignoring known-failure rule '{}': {}", rule.id, e));
+                false
+            }
+        });
+
+        let set = RegexSetBuilder::new(rules.iter().map(|rule| &rule.pattern))
+            .case_insensitive(true)
+            .build()
+            .unwrap_or_else(|_| RegexSet::empty());
+
+        (KnownFailureRules { rules, set }, warnings)
+    }
+
+    /// Rules whose pattern matches the line.
+    pub fn matches<'a>(&'a self, line: &str) -> impl Iterator<Item = &'a KnownFailureRule> {
+        self.set
+            .matches(line)
+            .into_iter()
+            .map(move |index| &self.rules[index])
+    }
+}
@@ -0,0 +1,163 @@
+// Byte-level line splitting and decoding for build process output.
+use encoding_rs::Encoding;
+use std::io::{ErrorKind, Read};
+
+/// Lines longer than this are split so a stream without line breaks can never
+/// turn into one unbounded line.
+const MAX_LINE_BYTES: usize = 64 * 1024;
+
+/// Decodes a raw line. Valid UTF-8 is used as-is; anything else goes through the
+/// configured fallback encoding (e.g. a non-English MSVC code page) or, without
+/// one, is decoded lossily. No line is ever discarded.
+fn decode(bytes: &[u8], fallback: Option<&'static Encoding>) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(_) => match fallback {
+            Some(encoding) => encoding.decode_without_bom_handling(bytes).0.into_owned(),
+            None => String::from_utf8_lossy(bytes).into_owned(),
+        },
+    }
+}
+
+/// Progress lines are the ones tools redraw in place: `[12/340] Compile ...`
+/// from UBT's action counter, or anything ending in a percentage.
+fn is_progress(line: &[u8]) -> bool {
+    let line = line.trim_ascii();
+    let counter = line.strip_prefix(b"[").is_some_and(|rest| {
+        let done = rest.iter().take_while(|b| b.is_ascii_digit()).count();
+        let rest = &rest[done..];
+        let total = rest
+            .strip_prefix(b"/")
+            .map(|rest| (rest.iter().take_while(|b| b.is_ascii_digit()).count(), rest));
+        done > 0
+            && total.is_some_and(|(digits, rest)| digits > 0 && rest.get(digits) == Some(&b']'))
+    });
+    let percentage = line
+        .strip_suffix(b"%")
+        .is_some_and(|rest| rest.last().is_some_and(u8::is_ascii_digit));
+    counter || percentage
+}
+
+/// Length of the longest prefix that does not end inside a UTF-8 character, so
+/// an overlong line is never split in the middle of one.
+fn split_point(line: &[u8]) -> usize {
+    match std::str::from_utf8(line) {
+        Err(e) if e.error_len().is_none() && e.valid_up_to() > 0 => e.valid_up_to(),
+        _ => line.len(),
+    }
+}
+
+/// Reads `source` to the end and calls `emit` for every line.
+///
+/// `\n`, `\r\n` and `\r\r\n` end a line as usual. A lone `\r` redraws the current line:
+/// a progress line is overwritten by whatever follows, so only its final state
+/// is kept, while any other line is committed before the redraw.
+pub fn read_lines<R: Read>(
+    mut source: R,
+    fallback: Option<&'static Encoding>,
+    mut emit: impl FnMut(String),
+) {
+    let mut buf = vec![0u8; 16 * 1024];
+    let mut line: Vec<u8> = Vec::new();
+    let mut after_cr = false;
+
+    loop {
+        let read = match source.read(&mut buf) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(_) => break,
+        };
+
+        for &byte in &buf[..read] {
+            if after_cr {
+                // Several `\r` in a row count as one, so the `\r\r\n` some
+                // tools write ends a single line.
+                if byte == b'\r' {
+                    continue;
+                }
+                after_cr = false;
+                if byte == b'\n' {
+                    emit(decode(&line, fallback));
+                    line.clear();
+                    continue;
+                }
+                // A bare '\r': the line is being redrawn.
+                if !line.is_empty() && !is_progress(&line) {
+                    emit(decode(&line, fallback));
+                }
+                line.clear();
+            }
+
+            match byte {
+                b'\r' => after_cr = true,
+                b'\n' => {
+                    emit(decode(&line, fallback));
+                    line.clear();
+                }
+                _ => {
+                    line.push(byte);
+                    if line.len() >= MAX_LINE_BYTES {
+                        let cut = split_point(&line);
+                        emit(decode(&line[..cut], fallback));
+                        line.drain(..cut);
+                    }
+                }
+            }
+        }
+    }
+
+    if !line.is_empty() {
+        emit(decode(&line, fallback));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(input: &[u8]) -> Vec<String> {
+        let mut lines = Vec::new();
+        read_lines(input, None, |line| lines.push(line));
+        lines
+    }
+
+    #[test]
+    fn splits_on_all_line_endings() {
+        assert_eq!(lines(b"a\nb\r\nc"), ["a", "b", "c"]);
+        assert_eq!(lines(b"a\r\r\nb\r\n"), ["a", "b"]);
+        assert_eq!(lines(b"a\n\nb\n"), ["a", "", "b"]);
+    }
+
+    #[test]
+    fn carriage_return_overwrites_progress_only() {
+        assert_eq!(
+            lines(b"[1/3] Compile A.cpp\r[2/3] Compile B.cpp\r[3/3] Link\r\nDone\n"),
+            ["[3/3] Link", "Done"]
+        );
+        assert_eq!(lines(b"Working 10%\rWorking 100%\n"), ["Working 100%"]);
+        assert_eq!(
+            lines(b"Parsing headers\rCompiling\n"),
+            ["Parsing headers", "Compiling"]
+        );
+    }
+
+    #[test]
+    fn keeps_invalid_utf8_lines() {
+        assert_eq!(
+            lines(b"ok\n\xff\xfe\nnext\n"),
+            ["ok", "\u{fffd}\u{fffd}", "next"]
+        );
+    }
+
+    #[test]
+    fn splits_long_lines_on_character_boundaries() {
+        let mut input = vec![b'a'; MAX_LINE_BYTES - 1];
+        input.extend_from_slice("é".as_bytes());
+        input.push(b'\n');
+        let lines = lines(&input);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].len(), MAX_LINE_BYTES - 1);
+        assert_eq!(lines[1], "é");
+    }
+}
@@ -0,0 +1,152 @@
+// Coalesces build log lines into chunked "build-log-chunk" events for the UI.
+use crate::build_log::LogLine;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::{Condvar, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+/// Pending lines are flushed at least this often.
+const FLUSH_INTERVAL: Duration = Duration::from_millis(50);
+
+/// A chunk is flushed early once this many lines are waiting.
+const MAX_CHUNK_LINES: usize = 500;
+
+/// When the UI cannot keep up, the oldest pending lines are dropped from the live
+/// stream beyond this point. They stay in the stored log and can be fetched with
+/// `get_build_logs`; the next chunk reports how many were skipped.
+const MAX_PENDING_LINES: usize = 5_000;
+
+/// Once the UI has acknowledged a chunk, no more than this many lines may be
+/// sent ahead of its latest acknowledgement.
+const MAX_UNACKED_LINES: usize = 10_000;
+
+/// A client that leaves a full window unacknowledged this long (e.g. after a
+/// webview reload) is treated as non-acking again, so live output keeps flowing.
+const ACK_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Serialize)]
+struct BuildLogChunk {
+    #[serde(rename = "buildId")]
+    build_id: String,
+    lines: Vec<LogLine>,
+    /// Lines dropped from the live stream right before this chunk.
+    skipped: usize,
+}
+
+#[derive(Default)]
+struct EmitterState {
+    pending: VecDeque<LogLine>,
+    skipped: usize,
+    sent_seq: Option<usize>,
+    acked_seq: Option<usize>,
+    /// When the window last filled up without a newer acknowledgement.
+    stalled_since: Option<Instant>,
+    closed: bool,
+}
+
+impl EmitterState {
+    /// Clients that never acknowledge chunks are not throttled.
+    fn window_full(&self) -> bool {
+        match (self.sent_seq, self.acked_seq) {
+            (Some(sent), Some(acked)) => sent.saturating_sub(acked) > MAX_UNACKED_LINES,
+            _ => false,
+        }
+    }
+
+    /// Whether sending must wait for an acknowledgement. Gives up on a client
+    /// that has not acknowledged anything for [`ACK_TIMEOUT`].
+    fn throttled(&mut self, now: Instant) -> bool {
+        if !self.window_full() {
+            self.stalled_since = None;
+            return false;
+        }
+        let stalled_since = *self.stalled_since.get_or_insert(now);
+        if now.duration_since(stalled_since) < ACK_TIMEOUT {
+            return true;
+        }
+        self.acked_seq = None;
+        self.stalled_since = None;
+        false
+    }
+}
+
+#[derive(Default)]
+pub struct LogEmitter {
+    state: Mutex<EmitterState>,
+    wake: Condvar,
+}
+
+impl LogEmitter {
+    pub fn enqueue(&self, line: LogLine) {
+        let mut state = self.state.lock().unwrap();
+        state.pending.push_back(line);
+        if state.pending.len() > MAX_PENDING_LINES {
+            state.pending.pop_front();
+            state.skipped += 1;
+        }
+        if state.pending.len() >= MAX_CHUNK_LINES {
+            self.wake.notify_one();
+        }
+    }
+
+    /// Records that the UI has rendered every line up to and including `seq`.
+    pub fn ack(&self, seq: usize) {
+        let mut state = self.state.lock().unwrap();
+        state.acked_seq = Some(state.acked_seq.map_or(seq, |acked| acked.max(seq)));
+        state.stalled_since = None;
+        self.wake.notify_one();
+    }
+
+    /// Flushes whatever is left and stops [`LogEmitter::run`].
+    pub fn close(&self) {
+        self.state.lock().unwrap().closed = true;
+        self.wake.notify_one();
+    }
+
+    /// Emits chunks until the emitter is closed and drained.
+    pub fn run(&self, app: &AppHandle, build_id: &str) {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            let deadline = Instant::now() + FLUSH_INTERVAL;
+            while !state.closed
+                && (state.pending.len() < MAX_CHUNK_LINES || state.throttled(Instant::now()))
+            {
+                let now = Instant::now();
+                if now >= deadline {
+                    break;
+                }
+                state = self.wake.wait_timeout(state, deadline - now).unwrap().0;
+            }
+
+            if state.pending.is_empty() && state.skipped == 0 {
+                if state.closed {
+                    return;
+                }
+                continue;
+            }
+            if !state.closed && state.throttled(Instant::now()) {
+                continue;
+            }
+
+            let count = state.pending.len().min(MAX_CHUNK_LINES);
+            let lines: Vec<LogLine> = state.pending.drain(..count).collect();
+            let skipped = std::mem::take(&mut state.skipped);
+            if let Some(last) = lines.last() {
+                state.sent_seq = Some(last.seq);
+            }
+
+            // Release the lock while emitting so readers are never blocked on the UI.
+            drop(state);
+            let _ = app.emit(
+                "build-log-chunk",
+                BuildLogChunk {
+                    build_id: build_id.to_string(),
+                    lines,
+                    skipped,
+                },
+            );
+            state = self.state.lock().unwrap();
+        }
+    }
+}
@@ -0,0 +1,142 @@
+// Server-side search over a build's full log.
+use crate::build_log::{FullLog, LogLine};
+use crate::diagnostics::{parse_diagnostic, Severity};
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Upper bound on matches returned in one page, regardless of the requested limit.
+const MAX_PAGE_MATCHES: usize = 1_000;
+
+/// Context is capped so a single match cannot pull in a large part of the log.
+const MAX_CONTEXT_LINES: usize = 50;
+
+const DEFAULT_PAGE_MATCHES: usize = 200;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SearchQuery {
+    /// Text or regex to look for; when empty every line matches.
+    pub pattern: Option<String>,
+    pub regex: bool,
+    pub case_sensitive: bool,
+    /// Lines of context to include before and after each match.
+    pub context: usize,
+    /// Only lines that parse as diagnostics of these severities match.
+    pub severities: Vec<Severity>,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+impl Default for SearchQuery {
+    fn default() -> Self {
+        SearchQuery {
+            pattern: None,
+            regex: false,
+            case_sensitive: false,
+            context: 0,
+            severities: Vec::new(),
+            offset: 0,
+            limit: DEFAULT_PAGE_MATCHES,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchMatch {
+    /// 1-based line number for display; `line.seq` is the 0-based index used by `get_build_logs`.
+    #[serde(rename = "lineNumber")]
+    pub line_number: usize,
+    pub line: LogLine,
+    pub severity: Option<Severity>,
+    pub before: Vec<LogLine>,
+    pub after: Vec<LogLine>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchResult {
+    pub matches: Vec<SearchMatch>,
+    #[serde(rename = "totalMatches")]
+    pub total_matches: usize,
+    #[serde(rename = "nextOffset")]
+    pub next_offset: Option<usize>,
+}
+
+fn build_matcher(query: &SearchQuery) -> Result<Option<Regex>, String> {
+    let pattern = match query.pattern.as_deref() {
+        Some(pattern) if !pattern.is_empty() => pattern,
+        _ => return Ok(None),
+    };
+
+    let source = if query.regex {
+        pattern.to_string()
+    } else {
+        regex::escape(pattern)
+    };
+
+    RegexBuilder::new(&source)
+        .case_insensitive(!query.case_sensitive)
+        .build()
+        .map(Some)
+        .map_err(|e| format!("Invalid search pattern: {}", e))
+}
+
+/// Scans the whole log, counting every match but only collecting the page
+/// `[offset, offset + limit)` together with its context lines.
+pub fn search_log(log: FullLog, query: &SearchQuery) -> Result<SearchResult, String> {
+    let matcher = build_matcher(query)?;
+    let context = query.context.min(MAX_CONTEXT_LINES);
+    let limit = query.limit.clamp(1, MAX_PAGE_MATCHES);
+
+    let mut before: VecDeque<LogLine> = VecDeque::with_capacity(context + 1);
+    let mut matches: Vec<SearchMatch> = Vec::new();
+    let mut total_matches = 0;
+
+    log.for_each_line(|line| {
+        // Feed trailing context to earlier matches that still need it.
+        for open in matches.iter_mut().rev() {
+            if open.after.len() >= context {
+                break;
+            }
+            open.after.push(line.clone());
+        }
+
+        let text_matches = matcher
+            .as_ref()
+            .is_none_or(|matcher| matcher.is_match(&line.text));
+        let severity = if text_matches {
+            parse_diagnostic(&line.text).map(|diagnostic| diagnostic.severity)
+        } else {
+            None
+        };
+        let severity_matches = query.severities.is_empty()
+            || severity.is_some_and(|severity| query.severities.contains(&severity));
+
+        if text_matches && severity_matches {
+            if total_matches >= query.offset && matches.len() < limit {
+                matches.push(SearchMatch {
+                    line_number: line.seq + 1,
+                    line: line.clone(),
+                    severity,
+                    before: before.iter().cloned().collect(),
+                    after: Vec::new(),
+                });
+            }
+            total_matches += 1;
+        }
+
+        if context > 0 {
+            if before.len() == context {
+                before.pop_front();
+            }
+            before.push_back(line);
+        }
+    })?;
+
+    let next_offset = query.offset + matches.len();
+    Ok(SearchResult {
+        matches,
+        total_matches,
+        next_offset: (next_offset < total_matches).then_some(next_offset),
+    })
+}
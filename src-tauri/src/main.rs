@@ -1,19 +1,88 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod build_log;
+mod compare;
+mod diagnostics;
+mod engine_association;
+mod engine_check;
+mod engine_details;
+mod engine_registry;
+mod engine_version;
+mod engine_watch;
+mod history;
+mod known_failures;
+mod log_decoder;
+mod log_emitter;
+mod log_search;
+mod receipt;
+mod report;
+mod retry;
+mod timing;
+mod trends;
+
+use build_log::{
+    log_file_path, prune_logs, read_disk_page, BuildLog, DiskRange, FullLog, LogLine, LogSource,
+    LogStream, PageLimits,
+};
+use compare::BuildComparison;
+use engine_check::EngineHealth;
+use engine_details::EngineDetails;
+use engine_version::{read_build_version, BuildVersion, EngineVersion};
+use engine_watch::{EnginesChanged, Fingerprint};
+use history::{BuildFilter, BuildHistory, BuildRecord};
+use known_failures::KnownFailureRules;
+use log_emitter::LogEmitter;
+use log_search::{search_log, SearchQuery, SearchResult};
+use receipt::BuildProducts;
+use report::ReportFormat;
+use retry::{BuildAttempt, RetryPolicy, TransientMatcher};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
-use std::io::{BufRead, BufReader};
+use std::io::Read;
 use std::path::{Path, PathBuf};
-use std::process::{Child, Command, Stdio};
-use std::sync::{Arc, Mutex};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread::JoinHandle;
+use std::time::Duration;
 use tauri::{AppHandle, Emitter, Manager, State};
+use trends::{RegressionPolicy, TrendSeries};
 
 // Types
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProjectConfig {
     pub name: String,
     pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry: Option<RetryPolicy>,
+}
+
+/// Per-engine settings, keyed by `EngineInstall.id` in `Config`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EngineProfile {
+    /// Display name used instead of the detected one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Build platform used instead of `Win64`.
+    #[serde(
+        rename = "defaultPlatform",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub default_platform: Option<String>,
+    /// Appended to every UnrealBuildTool invocation.
+    #[serde(rename = "extraUbtArgs", default)]
+    pub extra_ubt_args: Vec<String>,
+    /// `dotnet` executable used instead of the one on PATH.
+    #[serde(
+        rename = "dotnetPath",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub dotnet_path: Option<String>,
+    /// Environment variables set for UnrealBuildTool and the editor.
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +90,79 @@ pub struct Config {
     pub projects: Vec<ProjectConfig>,
     #[serde(rename = "unrealEnginePath")]
     pub unreal_engine_path: Option<String>,
+    #[serde(rename = "logRetentionDays", default = "default_log_retention_days")]
+    pub log_retention_days: u64,
+    #[serde(rename = "logRetentionMaxMb", default = "default_log_retention_max_mb")]
+    pub log_retention_max_mb: u64,
+    #[serde(
+        rename = "keepFinishedBuilds",
+        default = "default_keep_finished_builds"
+    )]
+    pub keep_finished_builds: usize,
+    #[serde(
+        rename = "keepFinishedBuildsMinutes",
+        default = "default_keep_finished_builds_minutes"
+    )]
+    pub keep_finished_builds_minutes: i64,
+    #[serde(
+        rename = "regressionThresholdPercent",
+        default = "default_regression_threshold_percent"
+    )]
+    pub regression_threshold_percent: f64,
+    #[serde(
+        rename = "regressionBaselineBuilds",
+        default = "default_regression_baseline_builds"
+    )]
+    pub regression_baseline_builds: usize,
+    /// Extra known-failure rule files shared by a team, in addition to the
+    /// per-project `.stellar/known-failures.json`.
+    #[serde(default, rename = "knownFailureRuleFiles")]
+    pub known_failure_rule_files: Vec<String>,
+    /// Encoding label (e.g. "windows-1252", "gbk", "shift_jis") used for build
+    /// output that is not valid UTF-8. Without it such output is decoded lossily.
+    #[serde(default, rename = "buildOutputEncoding")]
+    pub build_output_encoding: Option<String>,
+    /// Extra directories whose subdirectories are scanned for engines.
+    #[serde(default, rename = "engineScanRoots")]
+    pub engine_scan_roots: Vec<String>,
+    #[serde(default, rename = "engineProfiles")]
+    pub engine_profiles: BTreeMap<String, EngineProfile>,
+    /// Engine root directories added by hand.
+    #[serde(default, rename = "manualEngines")]
+    pub manual_engines: Vec<String>,
+}
+
+fn default_log_retention_days() -> u64 {
+    30
+}
+
+fn default_log_retention_max_mb() -> u64 {
+    1024
+}
+
+fn default_keep_finished_builds() -> usize {
+    10
+}
+
+fn default_keep_finished_builds_minutes() -> i64 {
+    30
+}
+
+fn default_regression_threshold_percent() -> f64 {
+    50.0
+}
+
+fn default_regression_baseline_builds() -> usize {
+    5
+}
+
+impl Config {
+    fn regression_policy(&self) -> RegressionPolicy {
+        RegressionPolicy {
+            threshold_percent: self.regression_threshold_percent,
+            window: self.regression_baseline_builds.max(1),
+        }
+    }
 }
 
 impl Default for Config {
@@ -28,16 +170,127 @@ impl Default for Config {
         Config {
             projects: vec![],
             unreal_engine_path: None,
+            log_retention_days: default_log_retention_days(),
+            log_retention_max_mb: default_log_retention_max_mb(),
+            keep_finished_builds: default_keep_finished_builds(),
+            keep_finished_builds_minutes: default_keep_finished_builds_minutes(),
+            regression_threshold_percent: default_regression_threshold_percent(),
+            regression_baseline_builds: default_regression_baseline_builds(),
+            known_failure_rule_files: vec![],
+            build_output_encoding: None,
+            engine_scan_roots: vec![],
+            manual_engines: vec![],
+            engine_profiles: BTreeMap::new(),
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl Config {
+    /// The profile for an engine, matched by id or, for ids saved with
+    /// different separators or casing, by path.
+    fn engine_profile(&self, engine_path: &str) -> Option<&EngineProfile> {
+        self.engine_profiles.get(engine_path).or_else(|| {
+            let key = engine_registry::path_key(Path::new(engine_path));
+            self.engine_profiles
+                .iter()
+                .find(|(id, _)| engine_registry::path_key(Path::new(id)) == key)
+                .map(|(_, profile)| profile)
+        })
+    }
+}
+
+/// How an engine was installed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EngineKind {
+    /// Installed through the Epic Games Launcher.
+    Launcher,
+    /// A binary build made with the Installed Build script.
+    InstalledBuild,
+    /// Built from source.
+    Source,
+}
+
+/// Where `detect_engines` found an engine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EngineSource {
+    /// One of the default Epic Games install directories.
+    KnownLocation,
+    /// The launcher's `LauncherInstalled.dat`.
+    LauncherManifest,
+    /// The per-user engine registry used for source builds.
+    Registry,
+    /// A subdirectory of a configured scan root.
+    ScanRoot,
+    /// An engine path added by hand in the config.
+    Manual,
+    /// Only known from a project's `EngineAssociation`.
+    Association,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct EngineInstall {
     pub id: String,
     pub name: String,
     pub path: String,
     pub version: Option<String>,
+    /// Contents of `Engine/Build/Build.version`, when the engine has one.
+    #[serde(rename = "buildVersion", default)]
+    pub build_version: Option<BuildVersion>,
+    pub kind: EngineKind,
+    pub source: EngineSource,
+    /// GUID the engine is registered under, as used by `EngineAssociation`.
+    #[serde(default)]
+    pub guid: Option<String>,
+}
+
+impl EngineInstall {
+    pub fn engine_version(&self) -> Option<EngineVersion> {
+        EngineVersion::resolve(self.version.as_deref(), self.build_version.as_ref())
+    }
+
+    /// Sort key: the release (hotfix, then release over preview), then launcher
+    /// installs over Installed Builds over source builds of that release, then
+    /// the changelist.
+    pub fn precedence(&self) -> (Option<EngineVersion>, u8, u64) {
+        let kind = match self.kind {
+            EngineKind::Launcher => 2,
+            EngineKind::InstalledBuild => 1,
+            EngineKind::Source => 0,
+        };
+        let version = self.engine_version();
+        (
+            version.map(|version| version.release()),
+            kind,
+            version.map_or(0, |version| version.changelist),
+        )
+    }
+}
+
+/// The engine a project's `EngineAssociation` points at.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectEngine {
+    /// Raw association; an empty string means the project sits in an engine tree.
+    pub association: Option<String>,
+    pub engine: Option<EngineInstall>,
+}
+
+/// Outcome of moving a project to another engine.
+#[derive(Debug, Clone, Serialize)]
+pub struct EngineSwitch {
+    /// The association written to the `.uproject`.
+    pub association: String,
+    #[serde(rename = "previousAssociation")]
+    pub previous_association: Option<String>,
+    #[serde(rename = "backupPath")]
+    pub backup_path: String,
+    #[serde(rename = "cleanedIntermediate")]
+    pub cleaned_intermediate: bool,
+    #[serde(rename = "regeneratedProjectFiles")]
+    pub regenerated_project_files: bool,
+    /// Follow-up steps that failed after the association was written.
+    pub warnings: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -53,28 +306,152 @@ pub struct BuildStatus {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BuildLogsResponse {
-    pub lines: Vec<String>,
+    pub lines: Vec<LogLine>,
     #[serde(rename = "nextIndex")]
     pub next_index: usize,
+    #[serde(rename = "totalLines")]
+    pub total_lines: usize,
     pub finished: bool,
 }
 
+const DEFAULT_LOG_PAGE_SIZE: usize = 5_000;
+const DEFAULT_LOG_PAGE_BYTES: usize = 1024 * 1024;
+
 #[derive(Debug, Clone, Serialize)]
-struct BuildLogPayload {
+struct BuildRegressionPayload {
     #[serde(rename = "buildId")]
     build_id: String,
-    line: String,
+    #[serde(flatten)]
+    regression: trends::DurationRegression,
 }
 
 // Build state management
 struct BuildProcess {
     child: Option<Child>,
     status: BuildStatus,
-    logs: Arc<Mutex<Vec<String>>>,
+    logs: Arc<Mutex<BuildLog>>,
+    emitter: Arc<LogEmitter>,
+    record: BuildRecord,
+    // Set once the final state has been written to history.
+    finalized: bool,
+    retry: Option<RetryState>,
+    // Engine profile the build started with; retries reuse it.
+    profile: Option<EngineProfile>,
+    // Exit of a failed attempt that may still be retried; the monitor decides
+    // once all of its output has been read.
+    pending_exit: Option<ExitStatus>,
+    // Set from a retryable exit until the next attempt starts, so status polls
+    // during the backoff do not finish the build.
+    retrying: bool,
+}
+
+/// Retry bookkeeping for builds whose project enables automatic retries.
+struct RetryState {
+    policy: RetryPolicy,
+    matcher: TransientMatcher,
+    encoding: Option<&'static encoding_rs::Encoding>,
+    attempt_started_at: String,
+    first_line: usize,
+}
+
+impl BuildProcess {
+    /// Snapshot of the build as it would be stored in history.
+    fn to_record(&self) -> BuildRecord {
+        let mut record = self.record.clone();
+        record.status = self.status.clone();
+        record.summary = self.logs.lock().ok().map(|log| log.summary().clone());
+        record.update_duration();
+        record
+    }
+
+    /// Polls the child process and updates the status once it has exited.
+    fn refresh_status(&mut self) {
+        if self.status.status != "running" || self.retrying {
+            return;
+        }
+
+        if let Some(ref mut child) = self.child {
+            match child.try_wait() {
+                Ok(Some(exit_status)) => {
+                    if !exit_status.success() && self.can_retry() {
+                        self.child = None;
+                        self.pending_exit = Some(exit_status);
+                        self.retrying = true;
+                    } else {
+                        self.apply_exit(exit_status);
+                    }
+                }
+                Ok(None) => {
+                    // Still running
+                }
+                Err(e) => {
+                    self.status.status = "error".to_string();
+                    self.status.error = Some(format!("Failed to check process: {}", e));
+                }
+            }
+        }
+    }
+
+    fn apply_exit(&mut self, exit_status: ExitStatus) {
+        let now = chrono::Utc::now().to_rfc3339();
+        self.status.finished_at = Some(now);
+        self.status.code = exit_status.code();
+
+        if exit_status.success() {
+            self.status.status = "success".to_string();
+        } else {
+            self.status.status = "error".to_string();
+            self.status.error = Some(format!("Process exited with code {:?}", exit_status.code()));
+        }
+    }
+
+    fn can_retry(&self) -> bool {
+        self.retry
+            .as_ref()
+            .is_some_and(|retry| self.record.attempts.len() + 1 < retry.policy.max_attempts)
+    }
+
+    /// Records the attempt that just ended and starts counting lines for the next one.
+    fn close_attempt(&mut self, code: Option<i32>, transient_match: Option<String>) {
+        let Some(retry) = self.retry.as_mut() else {
+            return;
+        };
+        let mut log = self.logs.lock().unwrap();
+        let summary = log.begin_attempt();
+        self.record.attempts.push(BuildAttempt {
+            attempt: self.record.attempts.len() + 1,
+            started_at: retry.attempt_started_at.clone(),
+            finished_at: Some(chrono::Utc::now().to_rfc3339()),
+            code,
+            first_line: retry.first_line,
+            line_count: log.len() - retry.first_line,
+            warnings: summary.warnings,
+            errors: summary.errors,
+            transient_match,
+            summary: Some(summary),
+        });
+        retry.first_line = log.len();
+        retry.attempt_started_at = chrono::Utc::now().to_rfc3339();
+    }
 }
 
 struct AppState {
     builds: Mutex<HashMap<String, BuildProcess>>,
+    history: Mutex<BuildHistory>,
+    expired: Mutex<HashSet<String>>,
+    /// Last engine detection result; never held together with the other locks.
+    engines: Mutex<Option<Vec<EngineInstall>>>,
+}
+
+impl AppState {
+    fn new(app: &AppHandle) -> Self {
+        AppState {
+            builds: Mutex::new(HashMap::new()),
+            history: Mutex::new(BuildHistory::load(get_data_dir(app).join("builds.json"))),
+            expired: Mutex::new(HashSet::new()),
+            engines: Mutex::new(None),
+        }
+    }
 }
 
 // Helper functions
@@ -86,22 +463,552 @@ fn get_config_path(app: &AppHandle) -> PathBuf {
     config_dir.join("config.json")
 }
 
-fn parse_version_from_name(name: &str) -> Option<String> {
-    // Try UE_X.X pattern
-    if let Some(caps) = regex::Regex::new(r"(?i)UE[_-]([0-9]+(?:\.[0-9]+)*)")
-        .ok()
-        .and_then(|re| re.captures(name))
-    {
-        return caps.get(1).map(|m| m.as_str().to_string());
+fn get_data_dir(app: &AppHandle) -> PathBuf {
+    app.path()
+        .app_data_dir()
+        .unwrap_or_else(|_| PathBuf::from("."))
+}
+
+fn get_log_dir(app: &AppHandle) -> PathBuf {
+    get_data_dir(app).join("logs")
+}
+
+fn load_config(app: &AppHandle) -> Result<Config, String> {
+    let config_path = get_config_path(app);
+
+    if !config_path.exists() {
+        return Ok(Config::default());
     }
-    // Try generic version pattern
-    if let Some(caps) = regex::Regex::new(r"([0-9]+(?:\.[0-9]+)*)")
-        .ok()
-        .and_then(|re| re.captures(name))
+
+    let contents =
+        fs::read_to_string(&config_path).map_err(|e| format!("Failed to read config: {}", e))?;
+
+    serde_json::from_str(&contents).map_err(|e| format!("Failed to parse config: {}", e))
+}
+
+/// Applies the configured log retention policy, skipping logs of builds still running.
+fn apply_log_retention(app: &AppHandle) {
+    let config = load_config(app).unwrap_or_default();
+    let active: HashSet<PathBuf> = app
+        .state::<AppState>()
+        .builds
+        .lock()
+        .unwrap()
+        .values()
+        .filter(|build| build.status.status == "running")
+        .filter_map(|build| build.record.log_path.as_ref().map(PathBuf::from))
+        .collect();
+
+    prune_logs(
+        &get_log_dir(app),
+        Duration::from_secs(config.log_retention_days.saturating_mul(24 * 60 * 60)),
+        config.log_retention_max_mb.saturating_mul(1024 * 1024),
+        &active,
+    );
+}
+
+/// Drops finished builds from memory unless they are among the most recent
+/// `keep_finished_builds` or finished less than `keep_finished_builds_minutes` ago.
+fn evict_finished_builds(state: &AppState, config: &Config) {
+    let now = chrono::Utc::now();
+    let max_age = chrono::Duration::try_minutes(config.keep_finished_builds_minutes)
+        .unwrap_or(chrono::TimeDelta::MAX);
+
+    let mut builds = state.builds.lock().unwrap();
+    let mut finished: Vec<_> = builds
+        .iter()
+        .filter(|(_, build)| build.finalized)
+        .map(|(id, build)| {
+            let finished_at = build
+                .status
+                .finished_at
+                .as_deref()
+                .and_then(history::parse_timestamp);
+            (finished_at, id.clone())
+        })
+        .collect();
+    finished.sort_by_key(|(finished_at, _)| std::cmp::Reverse(*finished_at));
+
+    let mut expired = state.expired.lock().unwrap();
+    for (finished_at, build_id) in finished.into_iter().skip(config.keep_finished_builds) {
+        if finished_at.is_some_and(|finished_at| now - finished_at < max_age) {
+            continue;
+        }
+        builds.remove(&build_id);
+        expired.insert(build_id);
+    }
+}
+
+/// Evicts finished builds by age even when no new build finishes, and repeats
+/// the log retention sweep for sessions that stay open for days.
+fn spawn_maintenance(app: AppHandle) {
+    const EVICTION_INTERVAL: Duration = Duration::from_secs(60);
+    const RETENTION_EVERY: u32 = 60;
+
+    std::thread::spawn(move || {
+        apply_log_retention(&app);
+        let mut ticks = 0u32;
+        loop {
+            std::thread::sleep(EVICTION_INTERVAL);
+            let config = load_config(&app).unwrap_or_default();
+            evict_finished_builds(&app.state::<AppState>(), &config);
+
+            ticks += 1;
+            if ticks.is_multiple_of(RETENTION_EVERY) {
+                apply_log_retention(&app);
+            }
+        }
+    });
+}
+
+/// Looks up a build that is not held in memory, either because it ran in an
+/// earlier session or because it was evicted.
+fn find_stored_build(state: &AppState, build_id: &str) -> Result<BuildRecord, String> {
+    if let Some(record) = state.history.lock().unwrap().get(build_id) {
+        return Ok(record.clone());
+    }
+
+    if state.expired.lock().unwrap().contains(build_id) {
+        Err("Build has expired and is no longer available".to_string())
+    } else {
+        Err("Build not found".to_string())
+    }
+}
+
+fn lookup_build(state: &AppState, build_id: &str) -> Result<BuildRecord, String> {
+    if let Some(build) = state.builds.lock().unwrap().get(build_id) {
+        return Ok(build.to_record());
+    }
+
+    find_stored_build(state, build_id)
+}
+
+/// Every line of a live or stored build, for whole-log scans.
+fn full_build_log(state: &AppState, build_id: &str) -> Result<FullLog, String> {
+    let live = state
+        .builds
+        .lock()
+        .unwrap()
+        .get(build_id)
+        .map(|build| build.logs.lock().unwrap().full_log());
+
+    match live {
+        Some(log) => Ok(log),
+        None => find_stored_build(state, build_id)?
+            .log_path
+            .map(|path| FullLog::Disk(PathBuf::from(path)))
+            .ok_or_else(|| "No log file was recorded for this build".to_string()),
+    }
+}
+
+fn open_path(path: &Path) -> Result<(), String> {
+    #[cfg(windows)]
+    let mut cmd = {
+        use std::os::windows::process::CommandExt;
+        let mut cmd = Command::new("explorer");
+        cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+        cmd
+    };
+
+    #[cfg(target_os = "macos")]
+    let mut cmd = Command::new("open");
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    let mut cmd = Command::new("xdg-open");
+
+    cmd.arg(path)
+        .spawn()
+        .map_err(|e| format!("Failed to open {:?}: {}", path, e))?;
+
+    Ok(())
+}
+
+/// Resolves the on-disk log of a live or persisted build.
+fn resolve_log_path(state: &AppState, build_id: &str) -> Result<PathBuf, String> {
+    let live_path = state
+        .builds
+        .lock()
+        .unwrap()
+        .get(build_id)
+        .map(|build| build.record.log_path.clone());
+    let log_path = match live_path {
+        Some(log_path) => log_path,
+        None => find_stored_build(state, build_id)?.log_path,
+    };
+
+    let path = log_path
+        .map(PathBuf::from)
+        .ok_or_else(|| "No log file was recorded for this build".to_string())?;
+
+    if !path.exists() {
+        return Err(format!(
+            "Log file {:?} no longer exists (removed by log retention?)",
+            path
+        ));
+    }
+
+    Ok(path)
+}
+
+fn parse_filter_date(
+    value: Option<String>,
+    label: &str,
+) -> Result<Option<chrono::DateTime<chrono::Utc>>, String> {
+    match value {
+        Some(value) => history::parse_timestamp(&value)
+            .map(Some)
+            .ok_or_else(|| format!("Invalid {} date: {}", label, value)),
+        None => Ok(None),
+    }
+}
+
+/// Stores a line and queues it for the UI. Queueing happens while the log is
+/// still locked so chunks preserve sequence order.
+fn append_log_line(logs: &Mutex<BuildLog>, emitter: &LogEmitter, stream: LogStream, text: String) {
+    if let Ok(mut guard) = logs.lock() {
+        for line in guard.push(stream, text) {
+            emitter.enqueue(line);
+        }
+    }
+}
+
+fn spawn_log_reader<R: Read + Send + 'static>(
+    logs: Arc<Mutex<BuildLog>>,
+    emitter: Arc<LogEmitter>,
+    source: R,
+    stream: LogStream,
+    encoding: Option<&'static encoding_rs::Encoding>,
+) -> JoinHandle<()> {
+    std::thread::spawn(move || {
+        log_decoder::read_lines(source, encoding, |line| {
+            append_log_line(&logs, &emitter, stream, line);
+        });
+    })
+}
+
+fn ubt_dll_path(engine_path: &str) -> PathBuf {
+    PathBuf::from(engine_path).join("Engine/Binaries/DotNET/UnrealBuildTool/UnrealBuildTool.dll")
+}
+
+fn dotnet_program(profile: Option<&EngineProfile>) -> &str {
+    profile
+        .and_then(|profile| profile.dotnet_path.as_deref())
+        .filter(|path| !path.trim().is_empty())
+        .unwrap_or("dotnet")
+}
+
+fn spawn_ubt(
+    engine_path: &str,
+    arguments: &[String],
+    project_path: &str,
+    profile: Option<&EngineProfile>,
+) -> Result<Child, String> {
+    let mut cmd = Command::new(dotnet_program(profile));
+    cmd.arg(ubt_dll_path(engine_path))
+        .args(arguments)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if let Some(profile) = profile {
+        cmd.envs(&profile.env);
+    }
+
+    if let Some(project_dir) = PathBuf::from(project_path).parent() {
+        cmd.current_dir(project_dir);
+    }
+
+    #[cfg(windows)]
     {
-        return caps.get(1).map(|m| m.as_str().to_string());
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+    }
+
+    cmd.spawn()
+        .map_err(|e| match known_failures::spawn_failure_hint(&e) {
+            Some(hint) => format!("Failed to start build: {}. {}", e, hint),
+            None => format!("Failed to start build: {}", e),
+        })
+}
+
+fn spawn_output_readers(
+    child: &mut Child,
+    logs: &Arc<Mutex<BuildLog>>,
+    emitter: &Arc<LogEmitter>,
+    encoding: Option<&'static encoding_rs::Encoding>,
+) -> Vec<JoinHandle<()>> {
+    let mut readers = Vec::new();
+    if let Some(stdout) = child.stdout.take() {
+        readers.push(spawn_log_reader(
+            Arc::clone(logs),
+            Arc::clone(emitter),
+            stdout,
+            LogStream::Stdout,
+            encoding,
+        ));
     }
-    None
+    if let Some(stderr) = child.stderr.take() {
+        readers.push(spawn_log_reader(
+            Arc::clone(logs),
+            Arc::clone(emitter),
+            stderr,
+            LogStream::Stderr,
+            encoding,
+        ));
+    }
+    readers
+}
+
+/// First transient-failure pattern matched by the current attempt's output.
+fn find_transient_match(state: &AppState, build_id: &str) -> Option<String> {
+    let (log, first_line, matcher) = {
+        let builds = state.builds.lock().unwrap();
+        let build = builds.get(build_id)?;
+        let retry = build.retry.as_ref()?;
+        let log = build.logs.lock().unwrap().full_log();
+        (log, retry.first_line, retry.matcher.clone())
+    };
+
+    let mut found = None;
+    let _ = log.for_each_line(|line| {
+        if found.is_none() && line.seq >= first_line && line.stream != LogStream::System {
+            found = matcher.find(&line.text).map(str::to_string);
+        }
+    });
+    found
+}
+
+/// Decides what to do with a failed attempt. Returns the readers of the next
+/// attempt when the build was restarted, or `None` once the build is over.
+fn retry_failed_attempt(
+    state: &AppState,
+    build_id: &str,
+    exit_status: ExitStatus,
+) -> Option<Vec<JoinHandle<()>>> {
+    let transient = find_transient_match(state, build_id);
+
+    let delay = {
+        let mut builds = state.builds.lock().unwrap();
+        let build = builds.get_mut(build_id)?;
+        build.pending_exit = None;
+        let Some(pattern) = transient else {
+            build.retrying = false;
+            build.apply_exit(exit_status);
+            return None;
+        };
+
+        build.close_attempt(exit_status.code(), Some(pattern.clone()));
+        let retry = build.retry.as_ref()?;
+        let failed = build.record.attempts.len();
+        let delay = retry.policy.backoff(failed);
+        append_log_line(
+            &build.logs,
+            &build.emitter,
+            LogStream::System,
+            format!(
+                "Attempt {} failed with a transient error (matched '{}'). Retrying in {}s (attempt {} of {}).",
+                failed,
+                pattern,
+                delay.as_secs(),
+                failed + 1,
+                retry.policy.max_attempts
+            ),
+        );
+        delay
+    };
+
+    // Sleep in short steps so a cancel during the backoff takes effect promptly.
+    let waiting_since = std::time::Instant::now();
+    let deadline = waiting_since + delay;
+    while std::time::Instant::now() < deadline {
+        if state
+            .builds
+            .lock()
+            .unwrap()
+            .get(build_id)
+            .is_none_or(|build| build.status.status != "running")
+        {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+
+    let mut builds = state.builds.lock().unwrap();
+    let build = builds.get_mut(build_id)?;
+    let waited = waiting_since.elapsed().as_millis() as i64;
+    build.record.retry_backoff_ms = Some(build.record.retry_backoff_ms.unwrap_or(0) + waited);
+    build.retrying = false;
+    if build.status.status != "running" {
+        return None;
+    }
+    let record = &build.record;
+    match spawn_ubt(
+        &record.engine_path,
+        &record.arguments,
+        &record.project_path,
+        build.profile.as_ref(),
+    ) {
+        Ok(mut child) => {
+            let encoding = build.retry.as_ref().and_then(|retry| retry.encoding);
+            let readers = spawn_output_readers(&mut child, &build.logs, &build.emitter, encoding);
+            build.child = Some(child);
+            build.pending_exit = None;
+            Some(readers)
+        }
+        Err(e) => {
+            append_log_line(&build.logs, &build.emitter, LogStream::System, e);
+            build.apply_exit(exit_status);
+            None
+        }
+    }
+}
+
+/// Parses compile timings for builds started with `-Timing`. Must run after the
+/// output readers have finished so the log is complete.
+fn collect_build_timing(state: &AppState, build_id: &str) -> Option<timing::TimingProfile> {
+    let (log, record) = {
+        let builds = state.builds.lock().unwrap();
+        let build = builds.get(build_id)?;
+        if !build.record.arguments.iter().any(|arg| arg == "-Timing") {
+            return None;
+        }
+        let log = build.logs.lock().unwrap().full_log();
+        (log, build.record.clone())
+    };
+
+    let intermediate_dir = Path::new(&record.project_path)
+        .parent()?
+        .join("Intermediate")
+        .join("Build")
+        .join(&record.platform);
+    let since = std::time::SystemTime::from(record.started_at()?);
+    timing::collect(log, &intermediate_dir, since)
+}
+
+/// Reads the target receipt of a successful build.
+fn collect_build_products(state: &AppState, build_id: &str) -> Option<BuildProducts> {
+    let record = {
+        let builds = state.builds.lock().unwrap();
+        let build = builds.get(build_id)?;
+        if build.status.status != "success" {
+            return None;
+        }
+        build.record.clone()
+    };
+
+    let project_dir = Path::new(&record.project_path).parent()?;
+    let receipt_path = receipt::find_receipt(
+        project_dir,
+        &record.target,
+        &record.platform,
+        &record.configuration,
+    )?;
+    receipt::read_receipt(&receipt_path, Path::new(&record.engine_path), project_dir)
+        .map_err(|e| eprintln!("Failed to read build products for {}: {}", build_id, e))
+        .ok()
+}
+
+/// Waits for the output readers to drain, then records the final build state in history.
+fn spawn_build_monitor(
+    app: AppHandle,
+    build_id: String,
+    readers: Vec<JoinHandle<()>>,
+    emitter: Arc<LogEmitter>,
+) {
+    std::thread::spawn(move || {
+        let config = load_config(&app).unwrap_or_default();
+        let state = app.state::<AppState>();
+        let mut readers = readers;
+        loop {
+            for reader in readers.drain(..) {
+                let _ = reader.join();
+            }
+
+            let retryable_exit = loop {
+                {
+                    let mut builds = state.builds.lock().unwrap();
+                    let build = match builds.get_mut(&build_id) {
+                        Some(build) => build,
+                        None => return,
+                    };
+                    build.refresh_status();
+                    if build.status.status != "running" {
+                        break None;
+                    }
+                    if let Some(exit_status) = build.pending_exit {
+                        break Some(exit_status);
+                    }
+                }
+                std::thread::sleep(Duration::from_millis(100));
+            };
+
+            let Some(exit_status) = retryable_exit else {
+                break;
+            };
+            match retry_failed_attempt(&state, &build_id, exit_status) {
+                Some(next_readers) => readers = next_readers,
+                None => break,
+            }
+        }
+        emitter.close();
+
+        // Reading timing files and hashing products can take a while, so do it
+        // before taking the locks again.
+        let timing = collect_build_timing(&state, &build_id);
+        let products = collect_build_products(&state, &build_id);
+
+        {
+            let mut builds = state.builds.lock().unwrap();
+            let build = match builds.get_mut(&build_id) {
+                Some(build) => build,
+                None => return,
+            };
+            if !build.record.attempts.is_empty() {
+                build.close_attempt(build.status.code, None);
+            }
+            if let Ok(mut log) = build.logs.lock() {
+                log.close();
+            }
+            let mut history = state.history.lock().unwrap();
+            build.record.regression = trends::detect_regression(
+                &build.to_record(),
+                &history.query(&BuildFilter::default()),
+                &config.regression_policy(),
+            );
+            if let Some(regression) = &build.record.regression {
+                let _ = app.emit(
+                    "build-duration-regression",
+                    BuildRegressionPayload {
+                        build_id: build_id.clone(),
+                        regression: regression.clone(),
+                    },
+                );
+            }
+            build.record.timing = timing;
+            build.record.products = products;
+
+            history.upsert(build.to_record());
+            build.finalized = true;
+        }
+
+        evict_finished_builds(&state, &config);
+        apply_log_retention(&app);
+    });
+}
+
+fn parse_version_from_name(name: &str) -> Option<String> {
+    static UE_VERSION: OnceLock<regex::Regex> = OnceLock::new();
+    static GENERIC_VERSION: OnceLock<regex::Regex> = OnceLock::new();
+    let ue_version = UE_VERSION.get_or_init(|| {
+        regex::Regex::new(r"(?i)UE[_-]([0-9]+(?:\.[0-9]+)*)").expect("valid version regex")
+    });
+    let generic_version = GENERIC_VERSION
+        .get_or_init(|| regex::Regex::new(r"([0-9]+(?:\.[0-9]+)*)").expect("valid version regex"));
+
+    // Try UE_X.X pattern, then a generic version pattern
+    ue_version
+        .captures(name)
+        .or_else(|| generic_version.captures(name))
+        .and_then(|caps| caps.get(1))
+        .map(|m| m.as_str().to_string())
 }
 
 fn should_skip_directory(name: &str) -> bool {
@@ -117,7 +1024,7 @@ fn should_skip_directory(name: &str) -> bool {
     skip_names.iter().any(|s| lower == *s)
 }
 
-fn is_engine_root(path: &PathBuf) -> bool {
+fn is_engine_root(path: &Path) -> bool {
     let engine_dir = path.join("Engine");
     if !engine_dir.is_dir() {
         return false;
@@ -127,6 +1034,44 @@ fn is_engine_root(path: &PathBuf) -> bool {
     binaries.is_dir() || build.is_dir()
 }
 
+/// Engines installed by the Epic Games Launcher carry its `.egstore` metadata.
+/// Others are Installed Builds when they have the marker file UAT writes, and
+/// source builds otherwise.
+fn classify_engine(path: &Path) -> EngineKind {
+    if path.join(".egstore").is_dir() {
+        EngineKind::Launcher
+    } else if path.join("Engine/Build/InstalledBuild.txt").is_file() {
+        EngineKind::InstalledBuild
+    } else {
+        EngineKind::Source
+    }
+}
+
+/// Builds an entry for an engine root found outside the launcher.
+fn describe_engine(path: &Path, source: EngineSource) -> EngineInstall {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let build_version = read_build_version(path);
+    let version = build_version
+        .as_ref()
+        .map(BuildVersion::short)
+        .or_else(|| parse_version_from_name(&name));
+    let normalized = path.to_string_lossy().to_string();
+
+    EngineInstall {
+        id: normalized.clone(),
+        name: format_label(&name, &version),
+        path: normalized,
+        version,
+        build_version,
+        kind: classify_engine(path),
+        source,
+        guid: None,
+    }
+}
+
 fn format_label(name: &str, version: &Option<String>) -> String {
     if let Some(v) = version {
         format!("Unreal Engine {}", v)
@@ -270,20 +1215,25 @@ fn derive_editor_target(project_path: &str) -> Result<String, String> {
 // Tauri Commands
 #[tauri::command]
 fn get_config(app: AppHandle) -> Result<Config, String> {
-    let config_path = get_config_path(&app);
-
-    if !config_path.exists() {
-        return Ok(Config::default());
-    }
-
-    let contents =
-        fs::read_to_string(&config_path).map_err(|e| format!("Failed to read config: {}", e))?;
-
-    serde_json::from_str(&contents).map_err(|e| format!("Failed to parse config: {}", e))
+    load_config(&app)
 }
 
 #[tauri::command]
 fn save_config(app: AppHandle, config: Config) -> Result<(), String> {
+    // Only newly added engine locations are checked, so a drive that is
+    // temporarily unplugged does not block saving.
+    let previous = load_config(&app).unwrap_or_default();
+    for path in &config.manual_engines {
+        if !previous.manual_engines.contains(path) && !is_engine_root(Path::new(path)) {
+            return Err(format!("No Unreal Engine found at {:?}", path));
+        }
+    }
+    for root in &config.engine_scan_roots {
+        if !previous.engine_scan_roots.contains(root) && !Path::new(root).is_dir() {
+            return Err(format!("Engine scan root {:?} is not a directory", root));
+        }
+    }
+
     let config_path = get_config_path(&app);
 
     if let Some(parent) = config_path.parent() {
@@ -297,12 +1247,8 @@ fn save_config(app: AppHandle, config: Config) -> Result<(), String> {
     fs::write(&config_path, contents).map_err(|e| format!("Failed to write config: {}", e))
 }
 
-#[tauri::command]
-fn detect_engines() -> Result<Vec<EngineInstall>, String> {
-    let mut installs = Vec::new();
-    let mut seen = std::collections::HashSet::new();
-
-    // Get base directories to scan
+/// The default Epic Games install directories.
+fn known_engine_dirs() -> Vec<PathBuf> {
     let mut base_dirs = Vec::new();
 
     #[cfg(windows)]
@@ -332,6 +1278,29 @@ fn detect_engines() -> Result<Vec<EngineInstall>, String> {
         base_dirs.push(PathBuf::from("/opt/Epic Games"));
     }
 
+    base_dirs
+}
+
+/// The launcher's lists of installed engines.
+#[cfg(windows)]
+fn launcher_manifest_paths() -> Vec<PathBuf> {
+    let program_data = PathBuf::from(
+        std::env::var("ProgramData").unwrap_or_else(|_| "C:\\ProgramData".to_string()),
+    );
+    vec![
+        program_data.join("Epic/UnrealEngineLauncher/LauncherInstalled.dat"),
+        program_data.join("Epic/EpicGamesLauncher/LauncherInstalled.dat"),
+    ]
+}
+
+/// Scans the disk, the launcher manifest, the engine registry and the
+/// configured locations for engines, newest first.
+fn scan_engines(app: &AppHandle) -> Vec<EngineInstall> {
+    let mut installs = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+
+    let base_dirs = known_engine_dirs();
+
     // Scan base directories
     for base_dir in &base_dirs {
         if let Ok(entries) = fs::read_dir(base_dir) {
@@ -341,116 +1310,536 @@ fn detect_engines() -> Result<Vec<EngineInstall>, String> {
                     continue;
                 }
 
-                let name = entry.file_name().to_string_lossy().to_string();
-                if should_skip_directory(&name) {
-                    continue;
-                }
+                let name = entry.file_name().to_string_lossy().to_string();
+                if should_skip_directory(&name) {
+                    continue;
+                }
+
+                let normalized = path.to_string_lossy().to_string();
+                if seen.contains(&normalized) {
+                    continue;
+                }
+
+                if !is_engine_root(&path) {
+                    continue;
+                }
+
+                let build_version = read_build_version(&path);
+                let version = build_version
+                    .as_ref()
+                    .map(BuildVersion::short)
+                    .or_else(|| parse_version_from_name(&name));
+                let label = format_label(&name, &version);
+
+                installs.push(EngineInstall {
+                    id: normalized.clone(),
+                    name: label,
+                    path: normalized.clone(),
+                    version,
+                    build_version,
+                    kind: classify_engine(&path),
+                    source: EngineSource::KnownLocation,
+                    guid: None,
+                });
+                seen.insert(normalized);
+            }
+        }
+    }
+
+    // Also check Windows launcher installed file
+    #[cfg(windows)]
+    {
+        let launcher_paths = launcher_manifest_paths();
+
+        for launcher_path in &launcher_paths {
+            if let Ok(contents) = fs::read_to_string(launcher_path) {
+                if let Ok(data) = serde_json::from_str::<serde_json::Value>(&contents) {
+                    if let Some(list) = data.get("InstallationList").and_then(|v| v.as_array()) {
+                        for item in list {
+                            if let Some(location) =
+                                item.get("InstallLocation").and_then(|v| v.as_str())
+                            {
+                                let path = PathBuf::from(location);
+                                let name = path
+                                    .file_name()
+                                    .map(|n| n.to_string_lossy().to_string())
+                                    .unwrap_or_default();
+
+                                if should_skip_directory(&name) {
+                                    continue;
+                                }
+
+                                let normalized = path.to_string_lossy().to_string();
+                                if seen.contains(&normalized) {
+                                    continue;
+                                }
+
+                                if !is_engine_root(&path) {
+                                    continue;
+                                }
+
+                                let build_version = read_build_version(&path);
+                                let version = build_version
+                                    .as_ref()
+                                    .map(BuildVersion::short)
+                                    .or_else(|| {
+                                        item.get("AppVersion")
+                                            .and_then(|v| v.as_str())
+                                            .map(|s| s.to_string())
+                                    })
+                                    .or_else(|| parse_version_from_name(&name));
+
+                                let display_name = item
+                                    .get("DisplayName")
+                                    .and_then(|v| v.as_str())
+                                    .unwrap_or(&name);
+
+                                let label = format_label(display_name, &version);
+
+                                installs.push(EngineInstall {
+                                    id: normalized.clone(),
+                                    name: label,
+                                    path: normalized.clone(),
+                                    version,
+                                    build_version,
+                                    kind: EngineKind::Launcher,
+                                    source: EngineSource::LauncherManifest,
+                                    guid: None,
+                                });
+                                seen.insert(normalized);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // Engines registered by source builds and the register_engine command
+    for registered in engine_registry::registered_engines() {
+        let key = engine_registry::path_key(&registered.path);
+        if let Some(existing) = installs
+            .iter_mut()
+            .find(|install| engine_registry::path_key(Path::new(&install.path)) == key)
+        {
+            existing.guid = Some(registered.guid);
+            continue;
+        }
+        if !is_engine_root(&registered.path) {
+            continue;
+        }
+        let mut install = describe_engine(&registered.path, EngineSource::Registry);
+        install.guid = Some(registered.guid);
+        installs.push(install);
+    }
+
+    // User-configured scan roots and manually added engines
+    let config = load_config(app).unwrap_or_default();
+    let mut candidates = Vec::new();
+    for root in &config.engine_scan_roots {
+        let root = PathBuf::from(root);
+        // A scan root may itself be an engine.
+        candidates.push((root.clone(), EngineSource::ScanRoot));
+        if let Ok(entries) = fs::read_dir(&root) {
+            for entry in entries.flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if entry.path().is_dir() && !should_skip_directory(&name) {
+                    candidates.push((entry.path(), EngineSource::ScanRoot));
+                }
+            }
+        }
+    }
+    for path in &config.manual_engines {
+        candidates.push((PathBuf::from(path), EngineSource::Manual));
+    }
+
+    for (path, source) in candidates {
+        let key = engine_registry::path_key(&path);
+        if installs
+            .iter()
+            .any(|install| engine_registry::path_key(Path::new(&install.path)) == key)
+        {
+            continue;
+        }
+        if !is_engine_root(&path) {
+            continue;
+        }
+        installs.push(describe_engine(&path, source));
+    }
+
+    // Newest first; engines without a version go last
+    installs.sort_by_key(|install| std::cmp::Reverse(install.precedence()));
+
+    for install in &mut installs {
+        if let Some(label) = config
+            .engine_profile(&install.id)
+            .and_then(|profile| profile.label.as_deref())
+            .filter(|label| !label.trim().is_empty())
+        {
+            install.name = label.to_string();
+        }
+    }
+
+    installs
+}
+
+/// Paths whose changes can add, remove or update an engine. The Windows
+/// registry cannot be polled this way; registering through Stellar refreshes
+/// the cache directly.
+fn watched_engine_paths(app: &AppHandle, installs: &[EngineInstall]) -> Vec<PathBuf> {
+    let config = load_config(app).unwrap_or_default();
+    let mut paths = known_engine_dirs();
+    paths.push(get_config_path(app));
+
+    #[cfg(windows)]
+    paths.extend(launcher_manifest_paths());
+
+    #[cfg(not(windows))]
+    paths.extend(engine_registry::install_ini_path());
+
+    paths.extend(config.engine_scan_roots.iter().map(PathBuf::from));
+    paths.extend(config.manual_engines.iter().map(PathBuf::from));
+    paths.extend(
+        installs
+            .iter()
+            .map(|install| Path::new(&install.path).join("Engine/Build/Build.version")),
+    );
+    paths
+}
+
+/// The detected engines, scanning only if nothing has been cached yet.
+fn cached_engines(app: &AppHandle) -> Vec<EngineInstall> {
+    let cached = app.state::<AppState>().engines.lock().unwrap().clone();
+    cached.unwrap_or_else(|| refresh_engines(app))
+}
+
+/// Rescans for engines and emits `engines-changed` when the result differs
+/// from the cached one.
+fn refresh_engines(app: &AppHandle) -> Vec<EngineInstall> {
+    let installs = scan_engines(app);
+    let previous = app
+        .state::<AppState>()
+        .engines
+        .lock()
+        .unwrap()
+        .replace(installs.clone());
+
+    if let Some(previous) = previous {
+        let changes = EnginesChanged::between(&previous, &installs);
+        if !changes.is_empty() {
+            let _ = app.emit("engines-changed", changes);
+        }
+    }
+    installs
+}
+
+/// Fills the cache in the background and refreshes it whenever one of the
+/// watched locations changes.
+fn spawn_engine_watcher(app: AppHandle) {
+    std::thread::spawn(move || {
+        let mut installs = refresh_engines(&app);
+        let mut fingerprint = Fingerprint::of(watched_engine_paths(&app, &installs));
+        loop {
+            std::thread::sleep(engine_watch::POLL_INTERVAL);
+            let current = Fingerprint::of(watched_engine_paths(&app, &installs));
+            if current != fingerprint {
+                installs = refresh_engines(&app);
+                // Watch the Build.version files of newly found engines as well.
+                fingerprint = Fingerprint::of(watched_engine_paths(&app, &installs));
+            }
+        }
+    });
+}
+
+/// Detected engines, served from the cache unless `refresh` is set.
+#[tauri::command(async)]
+fn detect_engines(app: AppHandle, refresh: Option<bool>) -> Result<Vec<EngineInstall>, String> {
+    if refresh.unwrap_or(false) {
+        Ok(refresh_engines(&app))
+    } else {
+        Ok(cached_engines(&app))
+    }
+}
+
+fn project_engine(app: &AppHandle, project_path: &str) -> Result<ProjectEngine, String> {
+    let project_path = Path::new(project_path);
+    let project_dir = project_path
+        .parent()
+        .ok_or_else(|| "Project directory is invalid".to_string())?;
+    let Some(raw) = engine_association::read_association(project_path)? else {
+        return Ok(ProjectEngine {
+            association: None,
+            engine: None,
+        });
+    };
+
+    let association = engine_association::parse_association(&raw, project_dir);
+    let installs = cached_engines(app);
+    let engine = engine_association::find_install(&association, &installs)
+        .cloned()
+        .or_else(|| {
+            engine_association::association_root(&association, project_dir, is_engine_root)
+                .map(|root| describe_engine(&root, EngineSource::Association))
+        });
+
+    Ok(ProjectEngine {
+        association: Some(raw),
+        engine,
+    })
+}
+
+/// Picks the engine for a build or editor launch. An explicitly requested engine
+/// wins but is checked against the project's association; otherwise the
+/// associated engine is used, then the configured default.
+fn select_engine(
+    app: &AppHandle,
+    project_path: &str,
+    requested: Option<String>,
+) -> Result<(String, Vec<String>), String> {
+    let requested = requested.filter(|path| !path.trim().is_empty());
+    let resolved = project_engine(app, project_path).unwrap_or(ProjectEngine {
+        association: None,
+        engine: None,
+    });
+    let mut warnings = Vec::new();
 
-                let normalized = path.to_string_lossy().to_string();
-                if seen.contains(&normalized) {
-                    continue;
-                }
+    let unresolved_warning = |association: &str| {
+        format!(
+            "EngineAssociation '{}' does not match any detected engine",
+            association
+        )
+    };
 
-                if !is_engine_root(&path) {
-                    continue;
+    let engine_path = match (requested, resolved.engine) {
+        (Some(requested), Some(engine)) => {
+            if engine_registry::path_key(Path::new(&requested))
+                != engine_registry::path_key(Path::new(&engine.path))
+            {
+                warnings.push(format!(
+                    "Project is associated with {} at {}, but {} was selected",
+                    engine.name, engine.path, requested
+                ));
+            }
+            requested
+        }
+        (Some(requested), None) => {
+            if let Some(association) = resolved.association.filter(|a| !a.is_empty()) {
+                warnings.push(unresolved_warning(&association));
+            }
+            requested
+        }
+        (None, Some(engine)) => engine.path,
+        (None, None) => {
+            let fallback = load_config(app)?.unreal_engine_path.ok_or_else(|| {
+                match &resolved.association {
+                    Some(association) if !association.is_empty() => {
+                        unresolved_warning(association) + " and no default engine is configured"
+                    }
+                    _ => "No Unreal Engine selected".to_string(),
                 }
+            })?;
+            if let Some(association) = resolved.association.filter(|a| !a.is_empty()) {
+                warnings.push(unresolved_warning(&association));
+            }
+            fallback
+        }
+    };
 
-                let version = parse_version_from_name(&name);
-                let label = format_label(&name, &version);
+    Ok((engine_path, warnings))
+}
 
-                installs.push(EngineInstall {
-                    id: normalized.clone(),
-                    name: label,
-                    path: normalized.clone(),
-                    version,
-                });
-                seen.insert(normalized);
-            }
+/// The engine a project is associated with through its `.uproject`.
+#[tauri::command]
+fn resolve_project_engine(app: AppHandle, project_path: String) -> Result<ProjectEngine, String> {
+    project_engine(&app, &project_path)
+}
+
+/// The value Unreal's version selector would write for an engine: the version
+/// for launcher installs, and the registration GUID otherwise (registering the
+/// engine first if needed).
+fn association_for(app: &AppHandle, engine: &EngineInstall) -> Result<String, String> {
+    if engine.kind == EngineKind::Launcher {
+        if let Some(version) = engine.engine_version() {
+            return Ok(format!("{}.{}", version.major, version.minor));
         }
     }
+    if let Some(guid) = &engine.guid {
+        return Ok(guid.clone());
+    }
+    let guid = engine_registry::register(Path::new(&engine.path), None)?;
+    refresh_engines(app);
+    Ok(guid)
+}
 
-    // Also check Windows launcher installed file
-    #[cfg(windows)]
-    {
-        let launcher_paths = [
-            PathBuf::from(
-                std::env::var("ProgramData").unwrap_or_else(|_| "C:\\ProgramData".to_string()),
-            )
-            .join("Epic/UnrealEngineLauncher/LauncherInstalled.dat"),
-            PathBuf::from(
-                std::env::var("ProgramData").unwrap_or_else(|_| "C:\\ProgramData".to_string()),
-            )
-            .join("Epic/EpicGamesLauncher/LauncherInstalled.dat"),
-        ];
-
-        for launcher_path in &launcher_paths {
-            if let Ok(contents) = fs::read_to_string(launcher_path) {
-                if let Ok(data) = serde_json::from_str::<serde_json::Value>(&contents) {
-                    if let Some(list) = data.get("InstallationList").and_then(|v| v.as_array()) {
-                        for item in list {
-                            if let Some(location) =
-                                item.get("InstallLocation").and_then(|v| v.as_str())
-                            {
-                                let path = PathBuf::from(location);
-                                let name = path
-                                    .file_name()
-                                    .map(|n| n.to_string_lossy().to_string())
-                                    .unwrap_or_default();
+fn regenerate_project_files(
+    engine: &EngineInstall,
+    project_path: &str,
+    profile: Option<&EngineProfile>,
+) -> Result<(), String> {
+    let mut arguments = vec![
+        "-ProjectFiles".to_string(),
+        format!("-Project={}", project_path),
+        "-Game".to_string(),
+        "-Progress".to_string(),
+    ];
+    if engine.kind != EngineKind::Source {
+        arguments.push("-Rocket".to_string());
+    }
+    let output = spawn_ubt(&engine.path, &arguments, project_path, profile)?
+        .wait_with_output()
+        .map_err(|e| format!("Failed to generate project files: {}", e))?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let last_line = stderr
+        .lines()
+        .chain(stdout.lines())
+        .rfind(|line| !line.trim().is_empty())
+        .unwrap_or("no output");
+    Err(format!(
+        "Failed to generate project files (code {}): {}",
+        output.status.code().unwrap_or(-1),
+        last_line.trim()
+    ))
+}
 
-                                if should_skip_directory(&name) {
-                                    continue;
-                                }
+/// Points a project at another engine. The `.uproject` is backed up and only
+/// its `EngineAssociation` value is rewritten. Cleaning `Intermediate` and
+/// regenerating project files run afterwards and report failures as warnings.
+/// Cleaning is refused while a build of the project is running.
+#[tauri::command(async)]
+fn switch_project_engine(
+    app: AppHandle,
+    project_path: String,
+    engine_path: String,
+    regenerate_project_files: Option<bool>,
+    clean_intermediate: Option<bool>,
+) -> Result<EngineSwitch, String> {
+    let project = PathBuf::from(&project_path);
+    if !project.is_file() {
+        return Err(format!("Project file not found at {:?}", project));
+    }
+    let root = PathBuf::from(&engine_path);
+    if !is_engine_root(&root) {
+        return Err(format!("No Unreal Engine found at {:?}", root));
+    }
 
-                                let normalized = path.to_string_lossy().to_string();
-                                if seen.contains(&normalized) {
-                                    continue;
-                                }
+    // Deleting Intermediate under a running build corrupts it, and fails halfway
+    // on Windows where its files are locked.
+    if clean_intermediate.unwrap_or(false) {
+        let project_key = engine_registry::path_key(&project);
+        let building = app
+            .state::<AppState>()
+            .builds
+            .lock()
+            .unwrap()
+            .values()
+            .any(|build| {
+                build.status.status == "running"
+                    && engine_registry::path_key(Path::new(&build.record.project_path))
+                        == project_key
+            });
+        if building {
+            return Err(
+                "A build of this project is running; wait for it to finish before cleaning Intermediate"
+                    .to_string(),
+            );
+        }
+    }
 
-                                if !is_engine_root(&path) {
-                                    continue;
-                                }
+    let key = engine_registry::path_key(&root);
+    let engine = cached_engines(&app)
+        .into_iter()
+        .find(|install| engine_registry::path_key(Path::new(&install.path)) == key)
+        .unwrap_or_else(|| describe_engine(&root, EngineSource::Manual));
+
+    let previous_association = engine_association::read_association(&project)?;
+    let association = association_for(&app, &engine)?;
+    let backup = engine_association::write_association(&project, &association)?;
+    let mut warnings = Vec::new();
+
+    let mut cleaned_intermediate = false;
+    if clean_intermediate.unwrap_or(false) {
+        let intermediate = project
+            .parent()
+            .map(|dir| dir.join("Intermediate"))
+            .filter(|dir| dir.is_dir());
+        match intermediate.map(fs::remove_dir_all) {
+            Some(Err(e)) => warnings.push(format!("Failed to clean Intermediate: {}", e)),
+            Some(Ok(())) => cleaned_intermediate = true,
+            None => {}
+        }
+    }
 
-                                let version = item
-                                    .get("AppVersion")
-                                    .and_then(|v| v.as_str())
-                                    .map(|s| s.to_string())
-                                    .or_else(|| parse_version_from_name(&name));
+    let mut regenerated_project_files = false;
+    if regenerate_project_files.unwrap_or(false) {
+        let config = load_config(&app).unwrap_or_default();
+        let profile = config.engine_profile(&engine.path);
+        match self::regenerate_project_files(&engine, &project_path, profile) {
+            Ok(()) => regenerated_project_files = true,
+            Err(e) => warnings.push(e),
+        }
+    }
 
-                                let display_name = item
-                                    .get("DisplayName")
-                                    .and_then(|v| v.as_str())
-                                    .unwrap_or(&name);
+    Ok(EngineSwitch {
+        association,
+        previous_association,
+        backup_path: backup.to_string_lossy().to_string(),
+        cleaned_intermediate,
+        regenerated_project_files,
+        warnings,
+    })
+}
 
-                                let label = format_label(display_name, &version);
+/// Runs a health checklist against an engine so half-installed engines are
+/// reported before a build fails on them.
+#[tauri::command(async)]
+fn check_engine(engine_path: String) -> Result<EngineHealth, String> {
+    let path = PathBuf::from(&engine_path);
+    if !is_engine_root(&path) {
+        return Err(format!("No Unreal Engine found at {:?}", path));
+    }
+    let source_build = classify_engine(&path) == EngineKind::Source;
+    Ok(engine_check::check_engine(&path, source_build))
+}
 
-                                installs.push(EngineInstall {
-                                    id: normalized.clone(),
-                                    name: label,
-                                    path: normalized.clone(),
-                                    version,
-                                });
-                                seen.insert(normalized);
-                            }
-                        }
-                    }
-                }
-            }
-        }
+/// Lists an engine's target platforms, engine plugins and Marketplace/Fab plugins.
+#[tauri::command(async)]
+fn get_engine_details(engine_path: String) -> Result<EngineDetails, String> {
+    let path = PathBuf::from(&engine_path);
+    if !is_engine_root(&path) {
+        return Err(format!("No Unreal Engine found at {:?}", path));
     }
+    Ok(engine_details::engine_details(&path))
+}
 
-    // Sort by version descending
-    installs.sort_by(|a, b| match (&b.version, &a.version) {
-        (Some(bv), Some(av)) => {
-            let a_parts: Vec<u32> = av.split('.').filter_map(|s| s.parse().ok()).collect();
-            let b_parts: Vec<u32> = bv.split('.').filter_map(|s| s.parse().ok()).collect();
-            b_parts.cmp(&a_parts)
-        }
-        (Some(_), None) => std::cmp::Ordering::Less,
-        (None, Some(_)) => std::cmp::Ordering::Greater,
-        (None, None) => std::cmp::Ordering::Equal,
-    });
+/// Registers an engine directory the way Unreal's version selector does, so
+/// projects can associate with it by GUID. Returns the GUID.
+#[tauri::command]
+fn register_engine(
+    app: AppHandle,
+    engine_path: String,
+    guid: Option<String>,
+) -> Result<String, String> {
+    let path = PathBuf::from(&engine_path);
+    if !is_engine_root(&path) {
+        return Err(format!("No Unreal Engine found at {:?}", path));
+    }
+    let guid = engine_registry::register(&path, guid)?;
+    std::thread::spawn(move || refresh_engines(&app));
+    Ok(guid)
+}
 
-    Ok(installs)
+/// Removes an engine registration. Returns `false` if the GUID was not registered.
+#[tauri::command]
+fn unregister_engine(app: AppHandle, guid: String) -> Result<bool, String> {
+    let removed = engine_registry::unregister(&guid)?;
+    if removed {
+        std::thread::spawn(move || refresh_engines(&app));
+    }
+    Ok(removed)
 }
 
 #[tauri::command]
@@ -458,120 +1847,186 @@ fn start_build(
     app: AppHandle,
     state: State<AppState>,
     project_path: String,
-    unreal_engine_path: String,
+    unreal_engine_path: Option<String>,
+    timing: Option<bool>,
 ) -> Result<String, String> {
     let build_id = uuid::Uuid::new_v4().to_string();
+    let (unreal_engine_path, engine_warnings) =
+        select_engine(&app, &project_path, unreal_engine_path)?;
 
-    let ubt_dll = PathBuf::from(&unreal_engine_path)
-        .join("Engine/Binaries/DotNET/UnrealBuildTool/UnrealBuildTool.dll");
+    let ubt_dll = ubt_dll_path(&unreal_engine_path);
 
     if !ubt_dll.exists() {
         return Err(format!("UnrealBuildTool not found at {:?}", ubt_dll));
     }
 
-    let target_name = derive_editor_target(&project_path)?;
+    let config = load_config(&app).unwrap_or_default();
+    let profile = config.engine_profile(&unreal_engine_path).cloned();
 
-    let dotnet_command = format!(
-        "dotnet \"{}\" {} Win64 Development -Project=\"{}\" -WaitMutex",
+    let target_name = derive_editor_target(&project_path)?;
+    let platform = profile
+        .as_ref()
+        .and_then(|profile| profile.default_platform.clone())
+        .filter(|platform| !platform.trim().is_empty())
+        .unwrap_or_else(|| "Win64".to_string());
+    let configuration = "Development".to_string();
+
+    let mut dotnet_command = format!(
+        "{} \"{}\" {} {} {} -Project=\"{}\" -WaitMutex",
+        dotnet_program(profile.as_ref()),
         ubt_dll.display(),
         target_name,
+        platform,
+        configuration,
         project_path
     );
 
-    let mut cmd = Command::new("dotnet");
-    cmd.arg(ubt_dll)
-        .arg(target_name)
-        .arg("Win64")
-        .arg("Development")
-        .arg(format!("-Project={}", project_path))
-        .arg("-WaitMutex")
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped());
-
-    if let Some(project_dir) = PathBuf::from(&project_path).parent() {
-        cmd.current_dir(project_dir);
+    let mut arguments = vec![
+        target_name.clone(),
+        platform.clone(),
+        configuration.clone(),
+        format!("-Project={}", project_path),
+        "-WaitMutex".to_string(),
+    ];
+    if timing.unwrap_or(false) {
+        // Makes the compiler report per-file and per-header timings.
+        arguments.push("-Timing".to_string());
+        dotnet_command.push_str(" -Timing");
     }
-
-    #[cfg(windows)]
-    {
-        use std::os::windows::process::CommandExt;
-        cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+    if let Some(profile) = &profile {
+        for arg in &profile.extra_ubt_args {
+            arguments.push(arg.clone());
+            dotnet_command.push(' ');
+            dotnet_command.push_str(arg);
+        }
     }
 
-    let mut child = cmd
-        .spawn()
-        .map_err(|e| format!("Failed to start build: {}", e))?;
+    let mut child = spawn_ubt(
+        &unreal_engine_path,
+        &arguments,
+        &project_path,
+        profile.as_ref(),
+    )?;
+
+    let project_rules = PathBuf::from(&project_path)
+        .parent()
+        .map(|dir| dir.join(".stellar").join("known-failures.json"));
+    let rule_files: Vec<&Path> = config
+        .known_failure_rule_files
+        .iter()
+        .map(Path::new)
+        .chain(project_rules.as_deref())
+        .collect();
+    let (rules, rule_warnings) = KnownFailureRules::load(&rule_files);
 
-    let logs = Arc::new(Mutex::new(Vec::new()));
+    let log_path = log_file_path(&get_log_dir(&app), &build_id);
+    let logs = Arc::new(Mutex::new(BuildLog::new(&log_path, Arc::new(rules))));
+    let emitter = Arc::new(LogEmitter::default());
     {
-        let mut guard = logs.lock().unwrap();
-        guard.push(format!("Running: {}", dotnet_command));
+        let emitter = Arc::clone(&emitter);
+        let app = app.clone();
+        let build_id = build_id.clone();
+        std::thread::spawn(move || emitter.run(&app, &build_id));
     }
 
-    let payload = BuildLogPayload {
-        build_id: build_id.clone(),
-        line: format!("Running: {}", dotnet_command),
-    };
-    let _ = app.emit("build-log", payload);
-
-    let stdout_logs = Arc::clone(&logs);
-    let stdout_app = app.clone();
-    let stdout_build = build_id.clone();
-    if let Some(stdout) = child.stdout.take() {
-        std::thread::spawn(move || {
-            let reader = BufReader::new(stdout);
-            for line in reader.lines() {
-                if let Ok(line) = line {
-                    if let Ok(mut guard) = stdout_logs.lock() {
-                        guard.push(line.clone());
-                    }
-                    let _ = stdout_app.emit(
-                        "build-log",
-                        BuildLogPayload {
-                            build_id: stdout_build.clone(),
-                            line,
-                        },
-                    );
-                }
-            }
-        });
+    for warning in engine_warnings.into_iter().chain(rule_warnings) {
+        append_log_line(&logs, &emitter, LogStream::System, warning);
     }
 
-    let stderr_logs = Arc::clone(&logs);
-    let stderr_app = app.clone();
-    let stderr_build = build_id.clone();
-    if let Some(stderr) = child.stderr.take() {
-        std::thread::spawn(move || {
-            let reader = BufReader::new(stderr);
-            for line in reader.lines() {
-                if let Ok(line) = line {
-                    if let Ok(mut guard) = stderr_logs.lock() {
-                        guard.push(line.clone());
-                    }
-                    let _ = stderr_app.emit(
-                        "build-log",
-                        BuildLogPayload {
-                            build_id: stderr_build.clone(),
-                            line,
-                        },
-                    );
-                }
+    let output_encoding = config.build_output_encoding.as_deref().and_then(|label| {
+        let encoding = encoding_rs::Encoding::for_label(label.as_bytes());
+        if encoding.is_none() {
+            append_log_line(
+                &logs,
+                &emitter,
+                LogStream::System,
+                format!("Unknown build output encoding '{}', using UTF-8", label),
+            );
+        }
+        encoding
+    });
+    append_log_line(
+        &logs,
+        &emitter,
+        LogStream::System,
+        format!("Running: {}", dotnet_command),
+    );
+
+    let retry = config
+        .projects
+        .iter()
+        .find(|project| project.path.eq_ignore_ascii_case(&project_path))
+        .and_then(|project| project.retry.clone())
+        .filter(|policy| policy.enabled && policy.max_attempts > 1)
+        .map(|policy| {
+            let (matcher, warnings) = policy.matcher();
+            for warning in warnings {
+                append_log_line(&logs, &emitter, LogStream::System, warning);
+            }
+            RetryState {
+                policy,
+                matcher,
+                encoding: output_encoding,
+                attempt_started_at: chrono::Utc::now().to_rfc3339(),
+                first_line: 0,
             }
         });
-    }
+    let readers = spawn_output_readers(&mut child, &logs, &emitter, output_encoding);
 
     let now = chrono::Utc::now().to_rfc3339();
+    let status = BuildStatus {
+        status: "running".to_string(),
+        code: None,
+        error: None,
+        started_at: Some(now),
+        finished_at: None,
+    };
+
+    let engine_name = PathBuf::from(&unreal_engine_path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let project_name = PathBuf::from(&project_path)
+        .file_stem()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let record = BuildRecord {
+        id: build_id.clone(),
+        project_path: project_path.clone(),
+        project_name,
+        engine_path: unreal_engine_path.clone(),
+        engine_version: read_build_version(Path::new(&unreal_engine_path))
+            .map(|version| version.short())
+            .or_else(|| parse_version_from_name(&engine_name)),
+        target: target_name,
+        platform,
+        configuration,
+        arguments,
+        status: status.clone(),
+        duration_ms: None,
+        summary: None,
+        log_path: Some(log_path.to_string_lossy().to_string()),
+        regression: None,
+        timing: None,
+        products: None,
+        attempts: Vec::new(),
+        retry_backoff_ms: None,
+    };
+
+    state.history.lock().unwrap().upsert(record.clone());
 
     let build_process = BuildProcess {
         child: Some(child),
-        status: BuildStatus {
-            status: "running".to_string(),
-            code: None,
-            error: None,
-            started_at: Some(now),
-            finished_at: None,
-        },
+        status,
         logs,
+        emitter: Arc::clone(&emitter),
+        record,
+        finalized: false,
+        retry,
+        profile,
+        pending_exit: None,
+        retrying: false,
     };
 
     state
@@ -580,46 +2035,20 @@ fn start_build(
         .unwrap()
         .insert(build_id.clone(), build_process);
 
+    spawn_build_monitor(app.clone(), build_id.clone(), readers, emitter);
+
     Ok(build_id)
 }
 
 #[tauri::command]
 fn get_build_status(state: State<AppState>, build_id: String) -> Result<BuildStatus, String> {
-    let mut builds = state.builds.lock().unwrap();
-
-    let build = builds
-        .get_mut(&build_id)
-        .ok_or_else(|| "Build not found".to_string())?;
-
-    // Check if process has finished
-    if build.status.status == "running" {
-        if let Some(ref mut child) = build.child {
-            match child.try_wait() {
-                Ok(Some(exit_status)) => {
-                    let now = chrono::Utc::now().to_rfc3339();
-                    build.status.finished_at = Some(now);
-                    build.status.code = exit_status.code();
-
-                    if exit_status.success() {
-                        build.status.status = "success".to_string();
-                    } else {
-                        build.status.status = "error".to_string();
-                        build.status.error =
-                            Some(format!("Process exited with code {:?}", exit_status.code()));
-                    }
-                }
-                Ok(None) => {
-                    // Still running
-                }
-                Err(e) => {
-                    build.status.status = "error".to_string();
-                    build.status.error = Some(format!("Failed to check process: {}", e));
-                }
-            }
-        }
+    if let Some(build) = state.builds.lock().unwrap().get_mut(&build_id) {
+        // Check if process has finished
+        build.refresh_status();
+        return Ok(build.status.clone());
     }
 
-    Ok(build.status.clone())
+    find_stored_build(&state, &build_id).map(|record| record.status)
 }
 
 #[tauri::command]
@@ -627,27 +2056,232 @@ fn get_build_logs(
     state: State<AppState>,
     build_id: String,
     from: usize,
+    page_size: Option<usize>,
+    max_bytes: Option<usize>,
 ) -> Result<BuildLogsResponse, String> {
-    let mut builds = state.builds.lock().unwrap();
+    let limits = PageLimits {
+        max_lines: page_size.unwrap_or(DEFAULT_LOG_PAGE_SIZE).max(1),
+        max_bytes: max_bytes.unwrap_or(DEFAULT_LOG_PAGE_BYTES).max(1),
+    };
+
+    let live = state.builds.lock().unwrap().get(&build_id).map(|build| {
+        let guard = build.logs.lock().unwrap();
+        (
+            guard.page(from, limits),
+            guard.len(),
+            build.status.status != "running",
+        )
+    });
 
-    let build = builds
-        .get_mut(&build_id)
-        .ok_or_else(|| "Build not found".to_string())?;
+    let (source, total_lines, finished) = match live {
+        Some(live) => live,
+        None => {
+            let record = find_stored_build(&state, &build_id)?;
+            let path = record
+                .log_path
+                .map(PathBuf::from)
+                .ok_or_else(|| "No log file was recorded for this build".to_string())?;
+            let total_lines = record.summary.map_or(0, |summary| summary.line_count);
+            (
+                LogSource::Disk(DiskRange::whole_file(path)),
+                total_lines,
+                true,
+            )
+        }
+    };
 
-    let guard = build.logs.lock().unwrap();
-    let lines: Vec<String> = guard.iter().skip(from).cloned().collect();
-    let next_index = guard.len();
-    let finished = build.status.status != "running";
+    // Disk reads happen after the locks are released so output keeps streaming.
+    let page = match source {
+        LogSource::Memory(page) => page,
+        LogSource::Disk(range) => read_disk_page(&range, from, limits)?,
+    };
 
     Ok(BuildLogsResponse {
-        lines,
-        next_index,
-        finished,
+        lines: page.lines,
+        next_index: page.next_index,
+        total_lines,
+        finished: finished && page.next_index >= total_lines,
     })
 }
 
+/// Acknowledges that the UI has rendered live output up to `seq`, which lets
+/// the log emitter keep streaming instead of skipping ahead.
+#[tauri::command]
+fn ack_build_log(state: State<AppState>, build_id: String, seq: usize) -> Result<(), String> {
+    if let Some(build) = state.builds.lock().unwrap().get(&build_id) {
+        build.emitter.ack(seq);
+    }
+    Ok(())
+}
+
+#[tauri::command(async)]
+fn search_build_log(
+    state: State<AppState>,
+    build_id: String,
+    query: SearchQuery,
+) -> Result<SearchResult, String> {
+    search_log(full_build_log(&state, &build_id)?, &query)
+}
+
+#[tauri::command]
+fn list_builds(
+    state: State<AppState>,
+    project_path: Option<String>,
+    status: Option<String>,
+    since: Option<String>,
+    until: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<BuildRecord>, String> {
+    let filter = BuildFilter {
+        project_path,
+        status,
+        since: parse_filter_date(since, "since")?,
+        until: parse_filter_date(until, "until")?,
+        limit,
+    };
+
+    // Live builds take precedence over the copy persisted when they started.
+    let builds = state.builds.lock().unwrap();
+    let history = state.history.lock().unwrap();
+    let records = history
+        .query(&BuildFilter {
+            status: None,
+            limit: None,
+            ..filter.clone()
+        })
+        .into_iter()
+        .map(|record| match builds.get(&record.id) {
+            Some(build) => build.to_record(),
+            None => record,
+        })
+        .filter(|record| {
+            filter
+                .status
+                .as_ref()
+                .is_none_or(|status| record.status.status == *status)
+        })
+        .take(filter.limit.unwrap_or(usize::MAX))
+        .collect();
+
+    Ok(records)
+}
+
+#[tauri::command]
+fn get_build(state: State<AppState>, build_id: String) -> Result<BuildRecord, String> {
+    lookup_build(&state, &build_id)
+}
+
+/// Files produced by a successful build, as listed in its target receipt.
+#[tauri::command]
+fn get_build_products(state: State<AppState>, build_id: String) -> Result<BuildProducts, String> {
+    let record = lookup_build(&state, &build_id)?;
+    record
+        .products
+        .ok_or_else(|| match record.status.status.as_str() {
+            "running" => "Build is still running".to_string(),
+            "success" => "No target receipt was found for this build".to_string(),
+            _ => "Build did not succeed, so it has no build products".to_string(),
+        })
+}
+
+/// Duration trends of successful builds, grouped by project, target, platform and
+/// configuration. Every filter is optional.
+#[tauri::command(async)]
+fn get_build_trends(
+    app: AppHandle,
+    state: State<AppState>,
+    project_path: Option<String>,
+    target: Option<String>,
+    platform: Option<String>,
+    configuration: Option<String>,
+) -> Result<Vec<TrendSeries>, String> {
+    let config = load_config(&app)?;
+    let records: Vec<BuildRecord> = state
+        .history
+        .lock()
+        .unwrap()
+        .query(&BuildFilter {
+            project_path,
+            status: Some("success".to_string()),
+            ..BuildFilter::default()
+        })
+        .into_iter()
+        .filter(|record| target.as_ref().is_none_or(|t| record.target == *t))
+        .filter(|record| platform.as_ref().is_none_or(|p| record.platform == *p))
+        .filter(|record| {
+            configuration
+                .as_ref()
+                .is_none_or(|c| record.configuration == *c)
+        })
+        .collect();
+
+    Ok(trends::build_trends(&records, &config.regression_policy()))
+}
+
+/// Diffs two builds; `base_id` is usually the last good build and `head_id` the broken one.
+#[tauri::command]
+fn compare_builds(
+    state: State<AppState>,
+    base_id: String,
+    head_id: String,
+) -> Result<BuildComparison, String> {
+    let base = lookup_build(&state, &base_id)?;
+    let head = lookup_build(&state, &head_id)?;
+    Ok(compare::compare_builds(&base, &head))
+}
+
+/// Writes a report for a finished build and returns its path. Without an
+/// `output_path` the report goes to the app data `reports` directory.
+#[tauri::command(async)]
+fn export_build_report(
+    app: AppHandle,
+    state: State<AppState>,
+    build_id: String,
+    format: ReportFormat,
+    output_path: Option<String>,
+) -> Result<String, String> {
+    let record = lookup_build(&state, &build_id)?;
+    if record.status.status == "running" {
+        return Err("Build is still running".to_string());
+    }
+
+    let contents = report::render(&record, format)?;
+    let path = match output_path {
+        Some(path) => PathBuf::from(path),
+        None => {
+            get_data_dir(&app)
+                .join("reports")
+                .join(format!("{}.{}", build_id, format.extension()))
+        }
+    };
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create report directory: {}", e))?;
+    }
+    fs::write(&path, contents).map_err(|e| format!("Failed to write report: {}", e))?;
+
+    Ok(path.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+fn get_build_log_path(state: State<AppState>, build_id: String) -> Result<String, String> {
+    resolve_log_path(&state, &build_id).map(|path| path.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+fn open_build_log(state: State<AppState>, build_id: String) -> Result<(), String> {
+    let path = resolve_log_path(&state, &build_id)?;
+    open_path(&path)
+}
+
 #[tauri::command]
-fn launch_editor(project_path: String, unreal_engine_path: String) -> Result<(), String> {
+fn launch_editor(
+    app: AppHandle,
+    project_path: String,
+    unreal_engine_path: Option<String>,
+) -> Result<Vec<String>, String> {
+    let (unreal_engine_path, warnings) = select_engine(&app, &project_path, unreal_engine_path)?;
     let editor_exe =
         PathBuf::from(&unreal_engine_path).join("Engine/Binaries/Win64/UnrealEditor.exe");
 
@@ -665,6 +2299,10 @@ fn launch_editor(project_path: String, unreal_engine_path: String) -> Result<(),
 
     let mut cmd = Command::new(&editor_exe);
     cmd.arg(&project_path);
+    let config = load_config(&app).unwrap_or_default();
+    if let Some(profile) = config.engine_profile(&unreal_engine_path) {
+        cmd.envs(&profile.env);
+    }
 
     #[cfg(windows)]
     {
@@ -676,27 +2314,35 @@ fn launch_editor(project_path: String, unreal_engine_path: String) -> Result<(),
     cmd.spawn()
         .map_err(|e| format!("Failed to launch Unreal Editor: {}", e))?;
 
-    Ok(())
+    Ok(warnings)
 }
 
 #[tauri::command]
 fn cancel_build(state: State<AppState>, build_id: String) -> Result<bool, String> {
     let mut builds = state.builds.lock().unwrap();
 
-    let build = builds
-        .get_mut(&build_id)
-        .ok_or_else(|| "Build not found".to_string())?;
+    let build = match builds.get_mut(&build_id) {
+        Some(build) => build,
+        None => {
+            drop(builds);
+            // Evicted builds have already finished, so there is nothing to cancel.
+            return find_stored_build(&state, &build_id).map(|_| false);
+        }
+    };
 
     if build.status.status != "running" {
         return Ok(false);
     }
 
+    // A build waiting to retry has no child; cancelling ends the backoff.
     if let Some(ref mut child) = build.child {
         let _ = child.kill();
-        let now = chrono::Utc::now().to_rfc3339();
-        build.status.status = "cancelled".to_string();
-        build.status.finished_at = Some(now);
+    } else if !build.retrying {
+        return Ok(false);
     }
+    let now = chrono::Utc::now().to_rfc3339();
+    build.status.status = "cancelled".to_string();
+    build.status.finished_at = Some(now);
 
     Ok(true)
 }
@@ -704,18 +2350,39 @@ fn cancel_build(state: State<AppState>, build_id: String) -> Result<bool, String
 fn main() {
     tauri::Builder::default()
         .plugin(tauri_plugin_dialog::init())
-        .manage(AppState {
-            builds: Mutex::new(HashMap::new()),
+        .setup(|app| {
+            app.manage(AppState::new(app.handle()));
+
+            spawn_maintenance(app.handle().clone());
+            spawn_engine_watcher(app.handle().clone());
+
+            Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             get_config,
             save_config,
             detect_engines,
+            resolve_project_engine,
+            check_engine,
+            get_engine_details,
+            register_engine,
+            unregister_engine,
+            switch_project_engine,
             start_build,
             get_build_status,
             get_build_logs,
+            ack_build_log,
+            search_build_log,
             cancel_build,
             launch_editor,
+            list_builds,
+            get_build,
+            get_build_products,
+            compare_builds,
+            get_build_trends,
+            export_build_report,
+            get_build_log_path,
+            open_build_log,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,151 @@
+// Build products read from the `.target` receipts UnrealBuildTool writes under
+// `Binaries/<Platform>/`.
+use crate::engine_version::BuildVersion;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProductFile {
+    pub path: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// `None` when the file no longer exists.
+    pub size: Option<u64>,
+    pub sha256: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildProducts {
+    #[serde(rename = "receiptPath")]
+    pub receipt_path: String,
+    #[serde(rename = "targetName")]
+    pub target_name: String,
+    #[serde(rename = "targetType")]
+    pub target_type: Option<String>,
+    pub platform: String,
+    pub configuration: String,
+    pub architecture: Option<String>,
+    pub version: Option<BuildVersion>,
+    #[serde(rename = "buildProducts")]
+    pub build_products: Vec<ProductFile>,
+    #[serde(rename = "runtimeDependencies")]
+    pub runtime_dependencies: Vec<ProductFile>,
+}
+
+#[derive(Deserialize)]
+struct ReceiptFile {
+    #[serde(rename = "Path")]
+    path: String,
+    #[serde(rename = "Type", default)]
+    kind: String,
+}
+
+#[derive(Deserialize)]
+struct Receipt {
+    #[serde(rename = "TargetName")]
+    target_name: String,
+    #[serde(rename = "TargetType")]
+    target_type: Option<String>,
+    #[serde(rename = "Platform")]
+    platform: String,
+    #[serde(rename = "Configuration")]
+    configuration: String,
+    #[serde(rename = "Architecture")]
+    architecture: Option<String>,
+    #[serde(rename = "Version")]
+    version: Option<BuildVersion>,
+    #[serde(rename = "BuildProducts", default)]
+    build_products: Vec<ReceiptFile>,
+    #[serde(rename = "RuntimeDependencies", default)]
+    runtime_dependencies: Vec<ReceiptFile>,
+}
+
+/// Receipts are named `<Target>.target` for Development builds and
+/// `<Target>-<Platform>-<Configuration>.target` otherwise.
+pub fn find_receipt(
+    project_dir: &Path,
+    target: &str,
+    platform: &str,
+    configuration: &str,
+) -> Option<PathBuf> {
+    let dir = project_dir.join("Binaries").join(platform);
+    let suffixed = dir.join(format!("{}-{}-{}.target", target, platform, configuration));
+    let plain = dir.join(format!("{}.target", target));
+    let candidates = if configuration == "Development" {
+        [plain, suffixed]
+    } else {
+        [suffixed, plain]
+    };
+    candidates.into_iter().find(|path| path.is_file())
+}
+
+fn sha256_file(path: &Path) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut File::open(path)?, &mut hasher)?;
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+fn expand_path(path: &str, engine_dir: &Path, project_dir: &Path) -> PathBuf {
+    let expanded = path
+        .replace("$(EngineDir)", &engine_dir.to_string_lossy())
+        .replace("$(ProjectDir)", &project_dir.to_string_lossy());
+    PathBuf::from(expanded)
+}
+
+/// Only files inside the project are hashed; runtime dependencies from the
+/// engine can run to gigabytes and never change between project builds.
+fn describe(file: ReceiptFile, engine_dir: &Path, project_dir: &Path) -> ProductFile {
+    let path = expand_path(&file.path, engine_dir, project_dir);
+    let size = fs::metadata(&path).ok().map(|metadata| metadata.len());
+    let sha256 = if size.is_some() && path.starts_with(project_dir) {
+        sha256_file(&path).ok()
+    } else {
+        None
+    };
+    ProductFile {
+        path: path.to_string_lossy().to_string(),
+        kind: file.kind,
+        size,
+        sha256,
+    }
+}
+
+/// Reads a receipt and resolves its files against the engine and project directories.
+pub fn read_receipt(
+    receipt_path: &Path,
+    engine_root: &Path,
+    project_dir: &Path,
+) -> Result<BuildProducts, String> {
+    let contents = fs::read_to_string(receipt_path)
+        .map_err(|e| format!("Failed to read target receipt: {}", e))?;
+    let receipt: Receipt = serde_json::from_str(&contents)
+        .map_err(|e| format!("Failed to parse target receipt: {}", e))?;
+
+    let engine_dir = engine_root.join("Engine");
+    Ok(BuildProducts {
+        receipt_path: receipt_path.to_string_lossy().to_string(),
+        target_name: receipt.target_name,
+        target_type: receipt.target_type,
+        platform: receipt.platform,
+        configuration: receipt.configuration,
+        architecture: receipt.architecture,
+        version: receipt.version,
+        build_products: receipt
+            .build_products
+            .into_iter()
+            .map(|file| describe(file, &engine_dir, project_dir))
+            .collect(),
+        runtime_dependencies: receipt
+            .runtime_dependencies
+            .into_iter()
+            .map(|file| describe(file, &engine_dir, project_dir))
+            .collect(),
+    })
+}
@@ -0,0 +1,485 @@
+// Build reports in JUnit, SARIF, HTML and Markdown formats.
+use crate::diagnostics::{Diagnostic, Severity};
+use crate::history::BuildRecord;
+use serde::Deserialize;
+use std::fmt::Write;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    Junit,
+    Sarif,
+    Html,
+    Markdown,
+}
+
+impl ReportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ReportFormat::Junit => "junit.xml",
+            ReportFormat::Sarif => "sarif",
+            ReportFormat::Html => "html",
+            ReportFormat::Markdown => "md",
+        }
+    }
+}
+
+/// Diagnostics with file paths rewritten relative to the project root.
+struct ReportContext<'a> {
+    record: &'a BuildRecord,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> ReportContext<'a> {
+    fn new(record: &'a BuildRecord) -> Self {
+        let project_root = Path::new(&record.project_path)
+            .parent()
+            .map(|dir| dir.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let diagnostics = record
+            .summary
+            .as_ref()
+            .map(|summary| summary.diagnostics.clone())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|mut diagnostic| {
+                diagnostic.file = diagnostic
+                    .file
+                    .map(|file| relative_to(&file, &project_root));
+                diagnostic
+            })
+            .collect();
+
+        ReportContext {
+            record,
+            diagnostics,
+        }
+    }
+
+    fn of_severity(&self, severity: Severity) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(move |diagnostic| diagnostic.severity == severity)
+    }
+
+    fn title(&self) -> String {
+        format!(
+            "{} {} {} {}",
+            self.record.project_name,
+            self.record.target,
+            self.record.platform,
+            self.record.configuration
+        )
+    }
+
+    fn duration_seconds(&self) -> f64 {
+        self.record.duration_ms.unwrap_or_default() as f64 / 1000.0
+    }
+}
+
+/// Makes `file` relative to `root` using forward slashes. Windows paths compare
+/// case-insensitively; files outside the root are returned unchanged.
+fn relative_to(file: &str, root: &str) -> String {
+    let file = file.replace('\\', "/");
+    let root = root.replace('\\', "/");
+    let root = root.trim_end_matches('/');
+    if root.is_empty() || file.len() <= root.len() {
+        return file;
+    }
+
+    // `None` when the root length falls inside a multi-byte character, in
+    // which case the file cannot be under the root.
+    let (Some(prefix), Some(rest)) = (file.get(..root.len()), file.get(root.len()..)) else {
+        return file;
+    };
+    let same_root = if cfg!(windows) {
+        prefix.eq_ignore_ascii_case(root)
+    } else {
+        prefix == root
+    };
+    match rest.strip_prefix('/') {
+        Some(relative) if same_root => relative.to_string(),
+        _ => file,
+    }
+}
+
+fn is_absolute(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    path.starts_with('/')
+        || (bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':')
+}
+
+/// Percent-encodes a forward-slash path for use in a URI. Colons are kept only
+/// where they cannot be mistaken for a scheme separator.
+fn encode_uri_path(path: &str, keep_colon: bool) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                encoded.push(byte as char)
+            }
+            b':' if keep_colon => encoded.push(':'),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// `file:` URI for an absolute path such as `C:/Program Files/x.cpp` or `/home/x.cpp`.
+fn file_uri(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let separator = if path.starts_with('/') { "" } else { "/" };
+    format!("file://{}{}", separator, encode_uri_path(&path, true))
+}
+
+fn location(diagnostic: &Diagnostic) -> String {
+    match (&diagnostic.file, diagnostic.line) {
+        (Some(file), Some(line)) => format!("{}:{}", file, line),
+        (Some(file), None) => file.clone(),
+        _ => String::new(),
+    }
+}
+
+fn escape_markup(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn escape_markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+pub fn render(record: &BuildRecord, format: ReportFormat) -> Result<String, String> {
+    let context = ReportContext::new(record);
+    match format {
+        ReportFormat::Junit => Ok(render_junit(&context)),
+        ReportFormat::Sarif => render_sarif(&context),
+        ReportFormat::Html => Ok(render_html(&context)),
+        ReportFormat::Markdown => Ok(render_markdown(&context)),
+    }
+}
+
+/// One test case for the build as a whole plus one failed case per distinct
+/// error. Warnings go to `system-out`, since JUnit has no notion of them.
+fn render_junit(context: &ReportContext) -> String {
+    let record = context.record;
+    let errors: Vec<&Diagnostic> = context.of_severity(Severity::Error).collect();
+    let build_failed = record.status.status != "success";
+    let failures = errors.len() + usize::from(build_failed);
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        "<testsuites name=\"Stellar\" tests=\"{}\" failures=\"{}\" time=\"{:.3}\">",
+        errors.len() + 1,
+        failures,
+        context.duration_seconds()
+    );
+    let _ = writeln!(
+        xml,
+        "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" time=\"{:.3}\" timestamp=\"{}\">",
+        escape_markup(&context.title()),
+        errors.len() + 1,
+        failures,
+        context.duration_seconds(),
+        escape_markup(record.status.started_at.as_deref().unwrap_or_default())
+    );
+
+    let _ = write!(
+        xml,
+        "    <testcase classname=\"{}\" name=\"build\" time=\"{:.3}\"",
+        escape_markup(&record.project_name),
+        context.duration_seconds()
+    );
+    if build_failed {
+        let message = record
+            .status
+            .error
+            .clone()
+            .unwrap_or_else(|| format!("Build {}", record.status.status));
+        let hints: Vec<String> = record
+            .summary
+            .iter()
+            .flat_map(|summary| &summary.hints)
+            .map(|hint| format!("{}: {}", hint.title, hint.fix))
+            .collect();
+        let _ = writeln!(
+            xml,
+            ">\n      <failure message=\"{}\">{}</failure>\n    </testcase>",
+            escape_markup(&message),
+            escape_markup(&hints.join("\n"))
+        );
+    } else {
+        xml.push_str(" />\n");
+    }
+
+    for diagnostic in &errors {
+        let _ = writeln!(
+            xml,
+            "    <testcase classname=\"{}\" name=\"{}\">\n      <failure message=\"{}\" type=\"{}\">{}</failure>\n    </testcase>",
+            escape_markup(diagnostic.file.as_deref().unwrap_or("build")),
+            escape_markup(&location(diagnostic)),
+            escape_markup(&diagnostic.message),
+            escape_markup(diagnostic.code.as_deref().unwrap_or("error")),
+            escape_markup(&diagnostic.message)
+        );
+    }
+
+    let warnings: Vec<String> = context
+        .of_severity(Severity::Warning)
+        .map(|diagnostic| format!("warning: {} {}", location(diagnostic), diagnostic.message))
+        .collect();
+    if !warnings.is_empty() {
+        let _ = writeln!(
+            xml,
+            "    <system-out>{}</system-out>",
+            escape_markup(&warnings.join("\n"))
+        );
+    }
+
+    xml.push_str("  </testsuite>\n</testsuites>\n");
+    xml
+}
+
+/// SARIF 2.1.0 with locations relative to the `PROJECTROOT` base URI.
+fn render_sarif(context: &ReportContext) -> Result<String, String> {
+    let record = context.record;
+    let project_root = Path::new(&record.project_path)
+        .parent()
+        .map(|dir| dir.to_string_lossy().replace('\\', "/"))
+        .unwrap_or_default();
+    let root_uri = format!("{}/", file_uri(&project_root));
+
+    let mut rule_ids: Vec<&str> = context
+        .diagnostics
+        .iter()
+        .filter_map(|diagnostic| diagnostic.code.as_deref())
+        .collect();
+    rule_ids.sort_unstable();
+    rule_ids.dedup();
+
+    let results: Vec<serde_json::Value> = context
+        .diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let mut result = serde_json::json!({
+                "ruleId": diagnostic.code.as_deref().unwrap_or("build"),
+                "level": match diagnostic.severity {
+                    Severity::Error => "error",
+                    Severity::Warning => "warning",
+                },
+                "message": { "text": diagnostic.message },
+            });
+            if let Some(file) = &diagnostic.file {
+                let mut region = serde_json::Map::new();
+                if let Some(line) = diagnostic.line {
+                    region.insert("startLine".to_string(), line.into());
+                }
+                if let Some(column) = diagnostic.column {
+                    region.insert("startColumn".to_string(), column.into());
+                }
+                // Files outside the project keep an absolute URI and no base.
+                let artifact = if is_absolute(file) {
+                    serde_json::json!({ "uri": file_uri(file) })
+                } else {
+                    serde_json::json!({
+                        "uri": encode_uri_path(&file.replace('\\', "/"), false),
+                        "uriBaseId": "PROJECTROOT",
+                    })
+                };
+                result["locations"] = serde_json::json!([{
+                    "physicalLocation": {
+                        "artifactLocation": artifact,
+                        "region": region,
+                    }
+                }]);
+            }
+            result
+        })
+        .collect();
+
+    let sarif = serde_json::json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "Stellar",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rule_ids
+                        .iter()
+                        .map(|id| serde_json::json!({ "id": id }))
+                        .collect::<Vec<_>>(),
+                }
+            },
+            "originalUriBaseIds": { "PROJECTROOT": { "uri": root_uri } },
+            "invocations": [{
+                "executionSuccessful": record.status.status == "success",
+                "commandLine": record.arguments.join(" "),
+                "startTimeUtc": record.status.started_at,
+                "endTimeUtc": record.status.finished_at,
+            }],
+            "results": results,
+        }]
+    });
+
+    serde_json::to_string_pretty(&sarif).map_err(|e| format!("Failed to serialize SARIF: {}", e))
+}
+
+fn overview_rows(context: &ReportContext) -> Vec<(&'static str, String)> {
+    let record = context.record;
+    let summary = record.summary.clone().unwrap_or_default();
+    vec![
+        ("Status", record.status.status.clone()),
+        ("Project", record.project_name.clone()),
+        ("Target", record.target.clone()),
+        ("Platform", record.platform.clone()),
+        ("Configuration", record.configuration.clone()),
+        (
+            "Engine",
+            record
+                .engine_version
+                .clone()
+                .unwrap_or_else(|| record.engine_path.clone()),
+        ),
+        (
+            "Started",
+            record.status.started_at.clone().unwrap_or_default(),
+        ),
+        ("Duration", format!("{:.1}s", context.duration_seconds())),
+        ("Errors", summary.errors.to_string()),
+        ("Warnings", summary.warnings.to_string()),
+        ("Actions", summary.actions_total.to_string()),
+    ]
+}
+
+fn render_markdown(context: &ReportContext) -> String {
+    let record = context.record;
+    let mut md = format!("# Build report: {}\n\n", context.title());
+
+    md.push_str("| | |\n|---|---|\n");
+    for (label, value) in overview_rows(context) {
+        let _ = writeln!(md, "| {} | {} |", label, escape_markdown_cell(&value));
+    }
+    if let Some(error) = &record.status.error {
+        let _ = writeln!(md, "\n> {}", error);
+    }
+
+    let hints = record.summary.iter().flat_map(|summary| &summary.hints);
+    let mut wrote_hints = false;
+    for hint in hints {
+        if !wrote_hints {
+            md.push_str("\n## Known issues\n\n");
+            wrote_hints = true;
+        }
+        let _ = writeln!(
+            md,
+            "- **{}**: {}\n  - Fix: {}",
+            hint.title, hint.explanation, hint.fix
+        );
+    }
+
+    for (heading, severity) in [("Errors", Severity::Error), ("Warnings", Severity::Warning)] {
+        let diagnostics: Vec<&Diagnostic> = context.of_severity(severity).collect();
+        if diagnostics.is_empty() {
+            continue;
+        }
+        let _ = write!(
+            md,
+            "\n## {}\n\n| Location | Code | Message |\n|---|---|---|\n",
+            heading
+        );
+        for diagnostic in diagnostics {
+            let _ = writeln!(
+                md,
+                "| `{}` | {} | {} |",
+                escape_markdown_cell(&location(diagnostic)),
+                escape_markdown_cell(diagnostic.code.as_deref().unwrap_or_default()),
+                escape_markdown_cell(&diagnostic.message)
+            );
+        }
+    }
+
+    md
+}
+
+fn render_html(context: &ReportContext) -> String {
+    let record = context.record;
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>Build report: {}</title>\n<style>\nbody {{ font-family: system-ui, sans-serif; margin: 2rem; color: #1d1d1f; }}\ntable {{ border-collapse: collapse; margin-bottom: 1.5rem; }}\ntd, th {{ border: 1px solid #d2d2d7; padding: 0.3rem 0.6rem; text-align: left; vertical-align: top; }}\n.error {{ color: #c62828; }}\n.warning {{ color: #a15c00; }}\ncode {{ font-family: Consolas, monospace; }}\n</style>\n</head>\n<body>\n<h1>Build report: {}</h1>\n<table>\n",
+        escape_markup(&context.title()),
+        escape_markup(&context.title())
+    );
+    for (label, value) in overview_rows(context) {
+        let _ = writeln!(
+            html,
+            "<tr><th>{}</th><td>{}</td></tr>",
+            label,
+            escape_markup(&value)
+        );
+    }
+    html.push_str("</table>\n");
+    if let Some(error) = &record.status.error {
+        let _ = writeln!(html, "<p class=\"error\">{}</p>", escape_markup(error));
+    }
+
+    let hints: Vec<_> = record
+        .summary
+        .iter()
+        .flat_map(|summary| &summary.hints)
+        .collect();
+    if !hints.is_empty() {
+        html.push_str("<h2>Known issues</h2>\n<ul>\n");
+        for hint in hints {
+            let _ = writeln!(
+                html,
+                "<li><strong>{}</strong>: {}<br>Fix: {}</li>",
+                escape_markup(&hint.title),
+                escape_markup(&hint.explanation),
+                escape_markup(&hint.fix)
+            );
+        }
+        html.push_str("</ul>\n");
+    }
+
+    for (heading, severity, class) in [
+        ("Errors", Severity::Error, "error"),
+        ("Warnings", Severity::Warning, "warning"),
+    ] {
+        let diagnostics: Vec<&Diagnostic> = context.of_severity(severity).collect();
+        if diagnostics.is_empty() {
+            continue;
+        }
+        let _ = writeln!(
+            html,
+            "<h2>{}</h2>\n<table>\n<tr><th>Location</th><th>Code</th><th>Message</th></tr>",
+            heading
+        );
+        for diagnostic in diagnostics {
+            let _ = writeln!(
+                html,
+                "<tr class=\"{}\"><td><code>{}</code></td><td>{}</td><td>{}</td></tr>",
+                class,
+                escape_markup(&location(diagnostic)),
+                escape_markup(diagnostic.code.as_deref().unwrap_or_default()),
+                escape_markup(&diagnostic.message)
+            );
+        }
+        html.push_str("</table>\n");
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
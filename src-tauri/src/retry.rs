@@ -0,0 +1,147 @@
+// Automatic retries for builds that fail on transient problems such as locked files.
+use crate::build_log::BuildSummary;
+use regex::{RegexSet, RegexSetBuilder};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Per-project retry settings. Retries only happen when `enabled` is set and a
+/// failed attempt printed a line matching one of `patterns`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Total attempts, including the first one.
+    #[serde(rename = "maxAttempts", default = "default_max_attempts")]
+    pub max_attempts: usize,
+    #[serde(rename = "backoffSeconds", default = "default_backoff_seconds")]
+    pub backoff_seconds: u64,
+    /// Each further retry waits this many times longer than the previous one.
+    #[serde(rename = "backoffMultiplier", default = "default_backoff_multiplier")]
+    pub backoff_multiplier: f64,
+    #[serde(rename = "maxBackoffSeconds", default = "default_max_backoff_seconds")]
+    pub max_backoff_seconds: u64,
+    /// Regular expressions matched case-insensitively against each output line.
+    #[serde(default = "default_transient_patterns")]
+    pub patterns: Vec<String>,
+}
+
+fn default_max_attempts() -> usize {
+    3
+}
+
+fn default_backoff_seconds() -> u64 {
+    10
+}
+
+fn default_backoff_multiplier() -> f64 {
+    2.0
+}
+
+fn default_max_backoff_seconds() -> u64 {
+    120
+}
+
+fn default_transient_patterns() -> Vec<String> {
+    [
+        r"being used by another process",
+        r"conflicting instance of UnrealBuildTool",
+        r"LNK1104: cannot open file",
+        r"C1083: Cannot open .*Permission denied",
+        r"Unable to (?:delete|move|copy|write) .*(?:Access is denied|Permission denied)",
+    ]
+    .iter()
+    .map(|pattern| pattern.to_string())
+    .collect()
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            enabled: false,
+            max_attempts: default_max_attempts(),
+            backoff_seconds: default_backoff_seconds(),
+            backoff_multiplier: default_backoff_multiplier(),
+            max_backoff_seconds: default_max_backoff_seconds(),
+            patterns: default_transient_patterns(),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before the retry that follows `failed_attempt` (1-based).
+    pub fn backoff(&self, failed_attempt: usize) -> Duration {
+        let exponent = failed_attempt.saturating_sub(1).min(16) as i32;
+        let seconds = self.backoff_seconds as f64 * self.backoff_multiplier.max(1.0).powi(exponent);
+        // `u64` maxima are not exact as `f64` and can round past what a
+        // `Duration` holds, so fall back to the configured maximum.
+        Duration::try_from_secs_f64(seconds.min(self.max_backoff_seconds as f64))
+            .unwrap_or(Duration::from_secs(self.max_backoff_seconds))
+    }
+
+    /// Compiles the patterns, skipping invalid ones with a warning.
+    pub fn matcher(&self) -> (TransientMatcher, Vec<String>) {
+        let mut warnings = Vec::new();
+        let patterns: Vec<String> = self
+            .patterns
+            .iter()
+            .filter(|pattern| match regex::Regex::new(pattern) {
+                Ok(_) => true,
+                Err(e) => {
+                    warnings.push(format!(
+                        "Ignoring transient-failure pattern '{}': {}",
+                        pattern, e
+                    ));
+                    false
+                }
+            })
+            .cloned()
+            .collect();
+
+        let set = RegexSetBuilder::new(&patterns)
+            .case_insensitive(true)
+            .build()
+            .unwrap_or_else(|_| RegexSet::empty());
+        (TransientMatcher { patterns, set }, warnings)
+    }
+}
+
+#[derive(Clone)]
+pub struct TransientMatcher {
+    patterns: Vec<String>,
+    set: RegexSet,
+}
+
+impl TransientMatcher {
+    /// The first pattern matching the line, if any.
+    pub fn find(&self, line: &str) -> Option<&str> {
+        self.set
+            .matches(line)
+            .iter()
+            .next()
+            .map(|index| self.patterns[index].as_str())
+    }
+}
+
+/// One run of UBT within a logical build. Every attempt writes to the same log;
+/// `first_line` and `line_count` locate its part of it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildAttempt {
+    pub attempt: usize,
+    #[serde(rename = "startedAt")]
+    pub started_at: String,
+    #[serde(rename = "finishedAt")]
+    pub finished_at: Option<String>,
+    pub code: Option<i32>,
+    #[serde(rename = "firstLine")]
+    pub first_line: usize,
+    #[serde(rename = "lineCount")]
+    pub line_count: usize,
+    pub warnings: usize,
+    pub errors: usize,
+    /// The transient-failure pattern that triggered a retry after this attempt.
+    #[serde(rename = "transientMatch")]
+    pub transient_match: Option<String>,
+    /// Diagnostics and hints from this attempt alone.
+    #[serde(default)]
+    pub summary: Option<BuildSummary>,
+}
@@ -0,0 +1,347 @@
+// Compile-time breakdown from UnrealBuildTool `-Timing` output.
+//
+// MSVC reports per-file frontend/backend times in the build output (`/Bt+`) and
+// include costs in `*.timing.txt` files; clang writes `-ftime-trace` JSON files
+// next to each object file.
+use crate::build_log::FullLog;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+use std::time::SystemTime;
+
+/// Only the slowest entries of each list are kept with the build record.
+const TOP_ENTRIES: usize = 50;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnitTiming {
+    pub file: String,
+    pub module: String,
+    #[serde(rename = "totalMs")]
+    pub total_ms: f64,
+    #[serde(rename = "frontendMs")]
+    pub frontend_ms: Option<f64>,
+    #[serde(rename = "backendMs")]
+    pub backend_ms: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleTiming {
+    pub module: String,
+    #[serde(rename = "totalMs")]
+    pub total_ms: f64,
+    pub units: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeaderTiming {
+    pub file: String,
+    /// Inclusive parse time summed over every unit that included the header.
+    #[serde(rename = "totalMs")]
+    pub total_ms: f64,
+    #[serde(rename = "includeCount")]
+    pub include_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimingProfile {
+    #[serde(rename = "totalMs")]
+    pub total_ms: f64,
+    #[serde(rename = "unitCount")]
+    pub unit_count: usize,
+    pub modules: Vec<ModuleTiming>,
+    #[serde(rename = "translationUnits")]
+    pub translation_units: Vec<UnitTiming>,
+    pub headers: Vec<HeaderTiming>,
+}
+
+#[derive(Default)]
+struct HeaderTotals {
+    file: String,
+    total_ms: f64,
+    include_count: usize,
+}
+
+#[derive(Default)]
+struct TimingCollector {
+    units: HashMap<String, UnitTiming>,
+    headers: HashMap<String, HeaderTotals>,
+}
+
+fn msvc_time_regex() -> &'static Regex {
+    // time(C:\...\c1xx.dll)=0.91202s < 21880424946 - 21882610456 > BB [C:\Path\Foo.cpp]
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"time\((?P<tool>[^)]+)\)=(?P<secs>[\d.]+)s.*\[(?P<file>[^\]]+)\]")
+            .expect("valid msvc timing regex")
+    })
+}
+
+fn msvc_include_regex() -> &'static Regex {
+    //         C:\Path\CoreMinimal.h: 0.51234s
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"^\t+(?P<file>.+?):\s*(?P<secs>[\d.]+)s\s*$").expect("valid msvc include regex")
+    })
+}
+
+fn normalize(path: &str) -> String {
+    path.replace('\\', "/").to_lowercase()
+}
+
+fn file_name(path: &str) -> &str {
+    path.rsplit(['/', '\\']).next().unwrap_or(path)
+}
+
+/// The module a file belongs to: the directory after the last `Source` segment,
+/// or for generated and unity files, the intermediate directory holding them.
+fn module_for(path: &str) -> String {
+    let segments: Vec<&str> = path.split(['/', '\\']).filter(|s| !s.is_empty()).collect();
+    if let Some(pos) = segments
+        .iter()
+        .rposition(|segment| segment.eq_ignore_ascii_case("Source"))
+    {
+        if let Some(module) = segments.get(pos + 1).filter(|_| pos + 2 < segments.len()) {
+            return module.to_string();
+        }
+    }
+    segments
+        .len()
+        .checked_sub(2)
+        .and_then(|index| segments.get(index))
+        .map(|segment| segment.to_string())
+        .unwrap_or_default()
+}
+
+impl TimingCollector {
+    fn unit(&mut self, path: &str, module: String) -> &mut UnitTiming {
+        let key = format!(
+            "{}/{}",
+            module.to_lowercase(),
+            file_name(path).to_lowercase()
+        );
+        self.units.entry(key).or_insert_with(|| UnitTiming {
+            file: path.to_string(),
+            module,
+            total_ms: 0.0,
+            frontend_ms: None,
+            backend_ms: None,
+        })
+    }
+
+    fn add_header(&mut self, path: &str, ms: f64) {
+        let totals = self.headers.entry(normalize(path)).or_default();
+        if totals.file.is_empty() {
+            totals.file = path.to_string();
+        }
+        totals.total_ms += ms;
+        totals.include_count += 1;
+    }
+
+    /// Picks up MSVC `/Bt+` timings printed in the build output.
+    fn observe_line(&mut self, line: &str) {
+        let Some(caps) = msvc_time_regex().captures(line) else {
+            return;
+        };
+        let Ok(secs) = caps["secs"].parse::<f64>() else {
+            return;
+        };
+        let ms = secs * 1000.0;
+        let tool = caps["tool"].to_lowercase();
+        let frontend = tool.ends_with("c1xx.dll") || tool.ends_with("c1.dll");
+        if !frontend && !tool.ends_with("c2.dll") {
+            return;
+        }
+        let path = caps["file"].trim().to_string();
+        let unit = self.unit(&path, module_for(&path));
+        if frontend {
+            unit.frontend_ms = Some(ms);
+        } else {
+            unit.backend_ms = Some(ms);
+        }
+        unit.total_ms = unit.frontend_ms.unwrap_or(0.0) + unit.backend_ms.unwrap_or(0.0);
+        // Prefer the real source path over an intermediate copy when both are seen.
+        if !normalize(&path).contains("/intermediate/") {
+            unit.file = path;
+        }
+    }
+
+    /// Reads every timing file under `dir` written since `since`.
+    fn scan_dir(&mut self, dir: &Path, since: SystemTime) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if metadata.is_dir() {
+                self.scan_dir(&path, since);
+                continue;
+            }
+            if metadata
+                .modified()
+                .map_or(true, |modified| modified < since)
+            {
+                continue;
+            }
+
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let lower = name.to_lowercase();
+            if lower.ends_with(".timing.txt") {
+                self.read_msvc_timing(&path);
+            } else if [".cpp.json", ".cc.json", ".c.json"]
+                .iter()
+                .any(|suffix| lower.ends_with(suffix))
+            {
+                self.read_clang_trace(&path);
+            }
+        }
+    }
+
+    fn read_msvc_timing(&mut self, path: &Path) {
+        let Ok(contents) = fs::read_to_string(path) else {
+            return;
+        };
+        let mut in_includes = false;
+        for line in contents.lines() {
+            if !line.starts_with('\t') && !line.trim().is_empty() {
+                in_includes = line.trim_end().eq_ignore_ascii_case("Include Headers:");
+                self.observe_line(line);
+                continue;
+            }
+            if !in_includes {
+                continue;
+            }
+            if let Some(caps) = msvc_include_regex().captures(line) {
+                if let Ok(secs) = caps["secs"].parse::<f64>() {
+                    self.add_header(caps["file"].trim(), secs * 1000.0);
+                }
+            }
+        }
+    }
+
+    fn read_clang_trace(&mut self, path: &Path) {
+        let Some(trace) = fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| serde_json::from_str::<serde_json::Value>(&contents).ok())
+        else {
+            return;
+        };
+        let Some(events) = trace.get("traceEvents").and_then(|e| e.as_array()) else {
+            return;
+        };
+
+        let unit_path = path.to_string_lossy();
+        let unit_path = unit_path
+            .strip_suffix(".json")
+            .unwrap_or(&unit_path)
+            .to_string();
+        let module = path
+            .parent()
+            .and_then(|dir| dir.file_name())
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let (mut total, mut frontend, mut backend) = (None, None, None);
+        for event in events {
+            let name = event.get("name").and_then(|n| n.as_str()).unwrap_or("");
+            let Some(ms) = event
+                .get("dur")
+                .and_then(|d| d.as_f64())
+                .map(|us| us / 1000.0)
+            else {
+                continue;
+            };
+            match name {
+                "Total ExecuteCompiler" => total = Some(ms),
+                "Total Frontend" => frontend = Some(ms),
+                "Total Backend" => backend = Some(ms),
+                "Source" => {
+                    if let Some(header) = event
+                        .get("args")
+                        .and_then(|a| a.get("detail"))
+                        .and_then(|d| d.as_str())
+                    {
+                        self.add_header(header, ms);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let Some(total) = total.or_else(|| Some(frontend? + backend.unwrap_or(0.0))) else {
+            return;
+        };
+        let unit = self.unit(&unit_path, module);
+        unit.total_ms = total;
+        unit.frontend_ms = frontend;
+        unit.backend_ms = backend;
+    }
+
+    /// Builds the profile, or `None` when no timing data was found.
+    fn finish(self) -> Option<TimingProfile> {
+        if self.units.is_empty() && self.headers.is_empty() {
+            return None;
+        }
+
+        let mut units: Vec<UnitTiming> = self.units.into_values().collect();
+        units.sort_by(|a, b| b.total_ms.total_cmp(&a.total_ms));
+
+        let mut modules: HashMap<String, ModuleTiming> = HashMap::new();
+        for unit in &units {
+            let module = modules
+                .entry(unit.module.to_lowercase())
+                .or_insert_with(|| ModuleTiming {
+                    module: unit.module.clone(),
+                    total_ms: 0.0,
+                    units: 0,
+                });
+            module.total_ms += unit.total_ms;
+            module.units += 1;
+        }
+        let mut modules: Vec<ModuleTiming> = modules.into_values().collect();
+        modules.sort_by(|a, b| b.total_ms.total_cmp(&a.total_ms));
+
+        let mut headers: Vec<HeaderTiming> = self
+            .headers
+            .into_values()
+            .map(|totals| HeaderTiming {
+                file: totals.file,
+                total_ms: totals.total_ms,
+                include_count: totals.include_count,
+            })
+            .collect();
+        headers.sort_by(|a, b| b.total_ms.total_cmp(&a.total_ms));
+        headers.truncate(TOP_ENTRIES);
+
+        let total_ms = units.iter().map(|unit| unit.total_ms).sum();
+        let unit_count = units.len();
+        units.truncate(TOP_ENTRIES);
+
+        Some(TimingProfile {
+            total_ms,
+            unit_count,
+            modules,
+            translation_units: units,
+            headers,
+        })
+    }
+}
+
+/// Gathers timing from the build output and from files under `intermediate_dir`
+/// written since the build started.
+pub fn collect(log: FullLog, intermediate_dir: &Path, since: SystemTime) -> Option<TimingProfile> {
+    let mut collector = TimingCollector::default();
+    if let Err(e) = log.for_each_line(|line| collector.observe_line(&line.text)) {
+        eprintln!("Failed to read build log for timing: {}", e);
+    }
+    collector.scan_dir(intermediate_dir, since);
+    collector.finish()
+}
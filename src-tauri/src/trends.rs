@@ -0,0 +1,199 @@
+// Build duration trends and regression detection against a rolling baseline.
+use crate::history::BuildRecord;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy)]
+pub struct RegressionPolicy {
+    /// A build is flagged when it is this many percent slower than the baseline.
+    pub threshold_percent: f64,
+    /// Number of previous successful builds the baseline is computed from.
+    pub window: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DurationRegression {
+    #[serde(rename = "baselineMs")]
+    pub baseline_ms: i64,
+    #[serde(rename = "durationMs")]
+    pub duration_ms: i64,
+    #[serde(rename = "deltaPercent")]
+    pub delta_percent: f64,
+    #[serde(rename = "thresholdPercent")]
+    pub threshold_percent: f64,
+}
+
+/// Builds are only comparable when they build the same thing the same way.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct SeriesKey {
+    #[serde(rename = "projectPath")]
+    pub project_path: String,
+    pub target: String,
+    pub platform: String,
+    pub configuration: String,
+}
+
+impl SeriesKey {
+    pub fn of(record: &BuildRecord) -> Self {
+        SeriesKey {
+            project_path: record.project_path.to_lowercase(),
+            target: record.target.clone(),
+            platform: record.platform.clone(),
+            configuration: record.configuration.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TrendPoint {
+    #[serde(rename = "buildId")]
+    pub build_id: String,
+    #[serde(rename = "startedAt")]
+    pub started_at: Option<String>,
+    #[serde(rename = "durationMs")]
+    pub duration_ms: i64,
+    #[serde(rename = "baselineMs")]
+    pub baseline_ms: Option<i64>,
+    #[serde(rename = "deltaPercent")]
+    pub delta_percent: Option<f64>,
+    pub regression: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TrendSeries {
+    #[serde(flatten)]
+    pub key: SeriesKey,
+    /// Successful builds, oldest first.
+    pub points: Vec<TrendPoint>,
+    /// Baseline the next build will be measured against.
+    #[serde(rename = "baselineMs")]
+    pub baseline_ms: Option<i64>,
+    #[serde(rename = "regressionCount")]
+    pub regression_count: usize,
+}
+
+/// Median of the given durations; robust against the odd cold or no-op build.
+fn median(durations: &[i64]) -> Option<i64> {
+    if durations.is_empty() {
+        return None;
+    }
+    let mut sorted = durations.to_vec();
+    sorted.sort_unstable();
+    let mid = sorted.len() / 2;
+    Some(if sorted.len().is_multiple_of(2) {
+        (sorted[mid - 1] + sorted[mid]) / 2
+    } else {
+        sorted[mid]
+    })
+}
+
+fn measure(
+    duration_ms: i64,
+    previous: &[i64],
+    policy: &RegressionPolicy,
+) -> Option<DurationRegression> {
+    let baseline_ms = median(previous).filter(|baseline| *baseline > 0)?;
+    let delta_percent = (duration_ms - baseline_ms) as f64 * 100.0 / baseline_ms as f64;
+    Some(DurationRegression {
+        baseline_ms,
+        duration_ms,
+        delta_percent,
+        threshold_percent: policy.threshold_percent,
+    })
+}
+
+fn is_trend_candidate(record: &BuildRecord) -> bool {
+    record.status.status == "success" && record.duration_ms.is_some()
+}
+
+/// Checks a just-finished build against earlier successful builds of the same
+/// series. `history` may be in any order and may include `record` itself.
+pub fn detect_regression(
+    record: &BuildRecord,
+    history: &[BuildRecord],
+    policy: &RegressionPolicy,
+) -> Option<DurationRegression> {
+    if !is_trend_candidate(record) {
+        return None;
+    }
+    let key = SeriesKey::of(record);
+    let started_at = record.started_at();
+
+    let mut previous: Vec<&BuildRecord> = history
+        .iter()
+        .filter(|other| other.id != record.id && is_trend_candidate(other))
+        .filter(|other| other.started_at() < started_at && SeriesKey::of(other) == key)
+        .collect();
+    previous.sort_by_key(|other| std::cmp::Reverse(other.started_at()));
+
+    let durations: Vec<i64> = previous
+        .iter()
+        .take(policy.window)
+        .filter_map(|other| other.duration_ms)
+        .collect();
+
+    measure(record.duration_ms?, &durations, policy)
+        .filter(|regression| regression.delta_percent > policy.threshold_percent)
+}
+
+/// Groups successful builds into per-series duration trends.
+pub fn build_trends(records: &[BuildRecord], policy: &RegressionPolicy) -> Vec<TrendSeries> {
+    let mut grouped: HashMap<SeriesKey, Vec<&BuildRecord>> = HashMap::new();
+    for record in records.iter().filter(|record| is_trend_candidate(record)) {
+        grouped
+            .entry(SeriesKey::of(record))
+            .or_default()
+            .push(record);
+    }
+
+    let mut series: Vec<TrendSeries> = grouped
+        .into_iter()
+        .map(|(key, mut records)| {
+            records.sort_by_key(|record| record.started_at());
+
+            let mut window: Vec<i64> = Vec::new();
+            let mut points = Vec::new();
+            for record in records {
+                let duration_ms = record.duration_ms.unwrap_or_default();
+                let measured = measure(duration_ms, &window, policy);
+                points.push(TrendPoint {
+                    build_id: record.id.clone(),
+                    started_at: record.status.started_at.clone(),
+                    duration_ms,
+                    baseline_ms: measured.as_ref().map(|m| m.baseline_ms),
+                    delta_percent: measured.as_ref().map(|m| m.delta_percent),
+                    regression: measured
+                        .is_some_and(|m| m.delta_percent > policy.threshold_percent),
+                });
+
+                window.push(duration_ms);
+                if window.len() > policy.window {
+                    window.remove(0);
+                }
+            }
+
+            TrendSeries {
+                key,
+                regression_count: points.iter().filter(|point| point.regression).count(),
+                baseline_ms: median(&window),
+                points,
+            }
+        })
+        .collect();
+
+    series.sort_by(|a, b| {
+        (
+            &a.key.project_path,
+            &a.key.target,
+            &a.key.platform,
+            &a.key.configuration,
+        )
+            .cmp(&(
+                &b.key.project_path,
+                &b.key.target,
+                &b.key.platform,
+                &b.key.configuration,
+            ))
+    });
+    series
+}